@@ -0,0 +1,13 @@
+#![no_main]
+#![allow(dead_code)]
+
+include!("../../src/bin/day3.rs");
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+    if let Some(input) = std::str::from_utf8(data)
+        .ok()
+        .and_then(advent_of_code_2025::inputs::normalize)
+    {
+        let _ = solve(&input, &[2, 12]);
+    }
+});
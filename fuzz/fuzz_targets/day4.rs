@@ -0,0 +1,21 @@
+#![no_main]
+#![allow(dead_code)]
+
+include!("../../src/bin/day4.rs");
+
+libfuzzer_sys::fuzz_target!(|data: &[u8]| {
+    if let Some(input) = std::str::from_utf8(data)
+        .ok()
+        .and_then(advent_of_code_2025::inputs::normalize)
+    {
+        for boundary in [Boundary::Empty, Boundary::Occupied, Boundary::Wrap] {
+            let rule = Rule::new(Neighbourhood::Moore, 1, 5, boundary);
+            let _ = solve(
+                &input,
+                &rule,
+                &[Semantics::Synchronous, Semantics::Sweep],
+                false,
+            );
+        }
+    }
+});
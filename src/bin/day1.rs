@@ -1,70 +1,216 @@
 use advent_of_code_2025::Args;
 use clap::Parser;
 use nom::{
-    IResult, Parser as nomParser, branch::alt, bytes::complete::tag, character::complete::u32,
-    combinator::map, sequence::preceded,
+    IResult, Parser as nomParser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{not_line_ending, one_of, space0, u64},
+    combinator::{eof, map, opt, verify},
+    multi::separated_list0,
+    sequence::{delimited, preceded, terminated},
 };
 
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{fmt, process};
+
+const DIAL_SIZE: u64 = 100;
 
 enum LockAction {
-    TurnRight(u32),
-    TurnLeft(u32),
+    TurnRight(u64),
+    TurnLeft(u64),
+    /// Turn the shorter way round to the given position
+    Set(u64),
 }
 
 fn parse_lock_action(input: &str) -> IResult<&str, LockAction> {
     alt((
-        map(preceded(tag("L"), u32), LockAction::TurnLeft),
-        map(preceded(tag("R"), u32), LockAction::TurnRight),
+        map(preceded((one_of("Ll"), space0), u64), LockAction::TurnLeft),
+        map(preceded((one_of("Rr"), space0), u64), LockAction::TurnRight),
+        map(
+            preceded((tag("="), space0), verify(u64, |&n: &u64| n < DIAL_SIZE)),
+            LockAction::Set,
+        ),
     ))
     .parse(input)
 }
 
-fn main() {
-    let args = Args::parse();
+fn parse_comment(input: &str) -> IResult<&str, &str> {
+    preceded(tag("#"), not_line_ending).parse(input)
+}
+
+/// Parses a line of comma separated actions, optionally followed by a `#` comment
+fn parse_lock_line(input: &str) -> IResult<&str, Vec<LockAction>> {
+    terminated(
+        delimited(
+            space0,
+            separated_list0(delimited(space0, tag(","), space0), parse_lock_action),
+            space0,
+        ),
+        (opt(parse_comment), eof),
+    )
+    .parse(input)
+}
+
+struct Dial {
+    pos: u64,
+    zero_count: u64,
+    /// Every rotation adds less than `2^64`, so this cannot overflow
+    zero_pass_count: u128,
+}
 
-    let input_file = File::open(args.input).expect("Could not find file");
-    let reader = BufReader::new(input_file);
-
-    let mut pos = 50;
-    let mut zero_count = 0;
-    let mut zero_pass_count = 0;
-    for line in reader.lines() {
-        let line = line.expect("Could not get line");
-        let start_pos = pos;
-        match parse_lock_action(&line) {
-            Ok((_, LockAction::TurnLeft(l))) => {
-                zero_pass_count += l / 100;
-                let l = l % 100;
-                if l >= pos {
-                    pos = 100 - (l - pos);
+impl Dial {
+    fn new(pos: u64) -> Dial {
+        Dial {
+            pos,
+            zero_count: 0,
+            zero_pass_count: 0,
+        }
+    }
+
+    fn apply(&mut self, action: &LockAction) {
+        let start_pos = self.pos;
+        match *action {
+            LockAction::TurnLeft(l) => {
+                self.zero_pass_count += (l / DIAL_SIZE) as u128;
+                let l = l % DIAL_SIZE;
+                if l >= self.pos {
+                    self.pos = DIAL_SIZE - (l - self.pos);
                     if start_pos != 0 {
-                        zero_pass_count += 1;
+                        self.zero_pass_count += 1;
                     }
-                    pos = pos % 100;
+                    self.pos %= DIAL_SIZE;
                 } else {
-                    pos -= l;
+                    self.pos -= l;
                 }
             }
-            Ok((_, LockAction::TurnRight(r))) => {
-                zero_pass_count += r / 100;
-                let r = r % 100;
-                pos = pos + r;
-                zero_pass_count += pos / 100;
-                pos = pos % 100;
+            LockAction::TurnRight(r) => {
+                self.zero_pass_count += (r / DIAL_SIZE) as u128;
+                let r = r % DIAL_SIZE;
+                self.pos += r;
+                self.zero_pass_count += (self.pos / DIAL_SIZE) as u128;
+                self.pos %= DIAL_SIZE;
             }
-            Err(e) => {
-                eprintln!("Could not parse line {} because {}", line, e)
+            LockAction::Set(target) => {
+                let right = (target + DIAL_SIZE - self.pos) % DIAL_SIZE;
+                let left = (self.pos + DIAL_SIZE - target) % DIAL_SIZE;
+                if left < right {
+                    self.apply(&LockAction::TurnLeft(left));
+                } else {
+                    self.apply(&LockAction::TurnRight(right));
+                }
+                // The turn above already counted the landing position
+                return;
             }
         }
-        if pos == 0 {
-            zero_count += 1;
+        if self.pos == 0 {
+            self.zero_count += 1;
         }
     }
+}
+
+/// A line that could not be parsed, with the 1-based column parsing stopped at
+struct LineError {
+    line: usize,
+    column: usize,
+    text: String,
+}
 
-    println!("Zero count: {}", zero_count);
-    println!("Zero pass count: {}", zero_pass_count);
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Could not parse line {}, column {}: {:?}",
+            self.line, self.column, self.text
+        )
+    }
+}
+
+/// Parses every line and turns the dial through all of the actions, or
+/// returns every line that could not be parsed
+fn solve(input: &str) -> Result<Dial, Vec<LineError>> {
+    let mut actions = Vec::new();
+    let mut errors = Vec::new();
+    for (line_num, line) in input.lines().enumerate() {
+        match parse_lock_line(line) {
+            Ok((_, line_actions)) => actions.extend(line_actions),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => errors.push(LineError {
+                line: line_num + 1,
+                column: line[..line.len() - e.input.len()].chars().count() + 1,
+                text: line.to_string(),
+            }),
+            Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never need more input"),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut dial = Dial::new(50);
+    for action in &actions {
+        dial.apply(action);
+    }
+    Ok(dial)
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let input = args.load_input(1).expect("Could not load input");
+
+    let dial = solve(&input).unwrap_or_else(|errors| {
+        for e in errors {
+            eprintln!("{}", e);
+        }
+        process::exit(1);
+    });
+
+    println!("Zero count: {}", dial.zero_count);
+    println!("Zero pass count: {}", dial.zero_pass_count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn huge_rotations_do_not_overflow() {
+        let input = "R18446744073709551615\n".repeat(101);
+        let Ok(dial) = solve(&input) else {
+            panic!("every line is a valid rotation");
+        };
+        assert_eq!(dial.zero_pass_count, 18_631_211_514_446_647_131);
+    }
+
+    #[test]
+    fn parses_comments_lists_and_set_actions() {
+        let Ok(dial) = solve("L68, R18 # comment\n=0\n# only a comment\nr 5\n") else {
+            panic!("every line is valid");
+        };
+        assert_eq!(dial.pos, 5);
+        assert_eq!(dial.zero_count, 2);
+        assert_eq!(dial.zero_pass_count, 2);
+    }
+
+    #[test]
+    fn reports_error_columns() {
+        let Err(errors) = solve("R1\n# ünïcode\nL2, →3\n") else {
+            panic!("the third line is invalid");
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!((errors[0].line, errors[0].column), (3, 3));
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        let options = generate::Day1Options {
+            rotations: 200,
+            max_distance: 500,
+        };
+        for seed in 0..5 {
+            let input = generate::day1(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            assert!(solve(&input).is_ok(), "seed {}", seed);
+        }
+    }
 }
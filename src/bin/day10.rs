@@ -1,8 +1,4 @@
-use std::{
-    cmp,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{cmp, fmt, process};
 
 use advent_of_code_2025::Args;
 use clap::Parser;
@@ -16,22 +12,46 @@ use nom::{
     sequence::{delimited, preceded},
 };
 
+#[derive(Parser)]
+#[command(about)]
+struct Day10Args {
+    #[command(flatten)]
+    args: Args,
+
+    /// try every combination of button presses instead of solving the
+    /// lights as a system of equations
+    #[arg(long)]
+    naive: bool,
+}
+
+/// The most buttons left free by elimination before searching their
+/// combinations would take too long
+const MAX_FREE_BUTTONS: usize = 24;
+
+#[derive(Debug, PartialEq)]
+enum InitError {
+    Unreachable,
+    TooManyFreeButtons(usize),
+}
+
+#[derive(Debug)]
 struct Machine {
     target: Vec<bool>,
     buttons: Vec<Vec<usize>>,
-    joltages: Vec<u64>,
 }
 
 impl Machine {
-    fn min_button_initialize(&self) -> u64 {
+    /// The reference solver: recurses through pressing or skipping every
+    /// button, so it takes `2^buttons` steps
+    fn naive_min_button_initialize(&self) -> Result<u64, InitError> {
         let mut state = vec![false; self.target.len()];
         self.help_min_button_initialize(&mut state, 0)
-            .expect("Could not find a solution")
+            .ok_or(InitError::Unreachable)
     }
 
-    fn help_min_button_initialize(&self, state: &mut Vec<bool>, button_idx: usize) -> Option<u64> {
+    fn help_min_button_initialize(&self, state: &mut [bool], button_idx: usize) -> Option<u64> {
         if button_idx >= self.buttons.len() {
-            match *state == self.target {
+            match *state == *self.target {
                 true => Some(0),
                 false => None,
             }
@@ -49,11 +69,79 @@ impl Machine {
         }
     }
 
-    fn apply_button_to_state(&self, button_idx: usize, state: &mut Vec<bool>) {
+    fn apply_button_to_state(&self, button_idx: usize, state: &mut [bool]) {
         for light in &self.buttons[button_idx] {
             state[*light] = !state[*light];
         }
     }
+
+    /// Finds the fewest presses that reach the target.
+    ///
+    /// Pressing a button twice undoes it, so each light is an equation over
+    /// GF(2): the buttons wired to it, added together, equal its target state.
+    /// Gauss-Jordan elimination leaves every pivot button determined by the
+    /// free ones, so only `2^free` combinations need to be tried rather than
+    /// `2^buttons`. Machines with more than [`MAX_FREE_BUTTONS`] are refused.
+    fn min_button_initialize(&self) -> Result<u64, InitError> {
+        // One row per light: which buttons toggle it, and whether it ends up on
+        let mut rows: Vec<(Vec<bool>, bool)> = self
+            .target
+            .iter()
+            .enumerate()
+            .map(|(light, &on)| {
+                let toggles = self.buttons.iter().map(|b| b.contains(&light)).collect();
+                (toggles, on)
+            })
+            .collect();
+
+        let mut pivots = Vec::new();
+        for button in 0..self.buttons.len() {
+            let rank = pivots.len();
+            let Some(pivot) = (rank..rows.len()).find(|&row| rows[row].0[button]) else {
+                continue;
+            };
+            rows.swap(rank, pivot);
+            let (pivot_toggles, pivot_on) = rows[rank].clone();
+            for (row, (toggles, on)) in rows.iter_mut().enumerate() {
+                if row != rank && toggles[button] {
+                    for (toggle, &pivot_toggle) in toggles.iter_mut().zip(&pivot_toggles) {
+                        *toggle ^= pivot_toggle;
+                    }
+                    *on ^= pivot_on;
+                }
+            }
+            pivots.push(button);
+        }
+
+        // A light no button can change still has to match
+        if rows[pivots.len()..].iter().any(|&(_, on)| on) {
+            return Err(InitError::Unreachable);
+        }
+
+        let free: Vec<usize> = (0..self.buttons.len())
+            .filter(|button| !pivots.contains(button))
+            .collect();
+        if free.len() > MAX_FREE_BUTTONS {
+            return Err(InitError::TooManyFreeButtons(free.len()));
+        }
+        (0..1u64 << free.len())
+            .map(|pressed| {
+                let pivot_presses = rows[..pivots.len()]
+                    .iter()
+                    .filter(|(toggles, on)| {
+                        let flips = free
+                            .iter()
+                            .enumerate()
+                            .filter(|&(i, &button)| pressed & (1 << i) != 0 && toggles[button])
+                            .count();
+                        *on ^ (flips % 2 == 1)
+                    })
+                    .count();
+                pressed.count_ones() as u64 + pivot_presses as u64
+            })
+            .min()
+            .ok_or(InitError::Unreachable)
+    }
 }
 
 fn parse_target(input: &str) -> IResult<&str, Vec<bool>> {
@@ -80,34 +168,202 @@ fn parse_joltages(input: &str) -> IResult<&str, Vec<u64>> {
 fn parse_machine(input: &str) -> IResult<&str, Machine> {
     let (input, target) = parse_target(input)?;
     let (input, buttons) = preceded(tag(" "), parse_buttons).parse(input)?;
-    let (input, joltages) = preceded(tag(" "), parse_joltages).parse(input)?;
-    Ok((
-        input,
-        Machine {
-            target: target,
-            buttons: buttons,
-            joltages: joltages,
-        },
-    ))
+    let (input, _joltages) = preceded(tag(" "), parse_joltages).parse(input)?;
+    Ok((input, Machine { target, buttons }))
+}
+
+enum MachineError {
+    InvalidMachine {
+        line: usize,
+    },
+    UnknownLight {
+        line: usize,
+        light: usize,
+        lights: usize,
+    },
+    DuplicateLight {
+        line: usize,
+        light: usize,
+    },
+    Init {
+        line: usize,
+        error: InitError,
+    },
+}
+
+impl fmt::Display for MachineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MachineError::InvalidMachine { line } => {
+                write!(f, "Could not parse machine on line {}", line)
+            }
+            MachineError::UnknownLight {
+                line,
+                light,
+                lights,
+            } => write!(
+                f,
+                "Machine on line {} has a button for light {} but only {} lights",
+                line, light, lights
+            ),
+            MachineError::DuplicateLight { line, light } => write!(
+                f,
+                "Machine on line {} has a button that lists light {} twice",
+                line, light
+            ),
+            MachineError::Init {
+                line,
+                error: InitError::Unreachable,
+            } => write!(f, "Machine on line {} cannot be initialized", line),
+            MachineError::Init {
+                line,
+                error: InitError::TooManyFreeButtons(free),
+            } => write!(
+                f,
+                "Machine on line {} has {} free buttons, more than the {} that can be searched",
+                line, free, MAX_FREE_BUTTONS
+            ),
+        }
+    }
+}
+
+/// Sums the fewest button presses needed to initialize every machine
+fn solve(input: &str, naive: bool) -> Result<u64, MachineError> {
+    let mut button_sum = 0;
+    for (line_num, text) in input.lines().enumerate() {
+        let line = line_num + 1;
+        let machine = match parse_machine(text) {
+            Ok(("", machine)) => machine,
+            _ => return Err(MachineError::InvalidMachine { line }),
+        };
+        for button in &machine.buttons {
+            for (i, &light) in button.iter().enumerate() {
+                if light >= machine.target.len() {
+                    return Err(MachineError::UnknownLight {
+                        line,
+                        light,
+                        lights: machine.target.len(),
+                    });
+                }
+                // The naive solver would toggle such a light once per listing
+                // while elimination only sees that the button is wired to it,
+                // so the two would disagree on what pressing it does
+                if button[..i].contains(&light) {
+                    return Err(MachineError::DuplicateLight { line, light });
+                }
+            }
+        }
+
+        let presses = if naive {
+            machine.naive_min_button_initialize()
+        } else {
+            machine.min_button_initialize()
+        };
+        button_sum += presses.map_err(|error| MachineError::Init { line, error })?;
+    }
+
+    Ok(button_sum)
 }
 
 fn main() {
-    let args = Args::parse();
-    let file = File::open(args.input).expect("Could not open file");
-    let reader = BufReader::new(file);
-
-    let machines: Vec<Machine> = reader
-        .lines()
-        .map(|line| {
-            parse_machine(&line.expect("Could not read line"))
-                .expect("Could not parse line")
-                .1
-        })
-        .collect();
+    let Day10Args { args, naive } = Day10Args::parse();
+    let input = args.load_input(10).expect("Could not load input");
 
-    let button_sum: u64 = machines
-        .iter()
-        .map(|machine| machine.min_button_initialize())
-        .sum();
+    let button_sum = solve(&input, naive).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
     println!("Min button presses to initialize: {}", button_sum);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use proptest::prelude::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Machines whose targets may or may not be reachable
+    fn machine() -> impl Strategy<Value = Machine> {
+        (1..8usize).prop_flat_map(|lights| {
+            (
+                prop::collection::vec(any::<bool>(), lights),
+                prop::collection::vec(
+                    prop::sample::subsequence((0..lights).collect::<Vec<_>>(), 1..=lights),
+                    1..10,
+                ),
+            )
+                .prop_map(|(target, buttons)| Machine { target, buttons })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn min_button_initialize_matches_naive(machine in machine()) {
+            prop_assert_eq!(machine.min_button_initialize(), machine.naive_min_button_initialize());
+        }
+    }
+
+    #[test]
+    fn rejects_buttons_for_unknown_lights() {
+        for naive in [false, true] {
+            assert!(matches!(
+                solve("[.] (1) {0}\n", naive),
+                Err(MachineError::UnknownLight { light: 1, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn solvers_disagree_on_a_light_listed_twice() {
+        let machine = Machine {
+            target: vec![true],
+            buttons: vec![vec![0, 0]],
+        };
+        assert_eq!(
+            machine.naive_min_button_initialize(),
+            Err(InitError::Unreachable)
+        );
+        assert_eq!(machine.min_button_initialize(), Ok(1));
+    }
+
+    #[test]
+    fn rejects_buttons_listing_a_light_twice() {
+        for naive in [false, true] {
+            assert!(matches!(
+                solve("[#] (0,0) {0}\n", naive),
+                Err(MachineError::DuplicateLight { light: 0, .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_too_many_free_buttons() {
+        let input = format!("[.] {}{{0}}\n", "(0) ".repeat(70));
+        assert!(matches!(
+            solve(&input, false),
+            Err(MachineError::Init {
+                error: InitError::TooManyFreeButtons(69),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn naive_and_fast_agree_on_generated_inputs() {
+        let options = generate::Day10Options {
+            machines: 20,
+            max_lights: 6,
+            max_buttons: 6,
+            max_joltage: 20,
+        };
+        for seed in 0..5 {
+            let input = generate::day10(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            let (Ok(naive), Ok(fast)) = (solve(&input, true), solve(&input, false)) else {
+                panic!("seed {} cannot be initialized", seed);
+            };
+            assert_eq!(naive, fast, "seed {}", seed);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use std::{collections::HashMap, fmt, process};
+
+use advent_of_code_2025::Args;
+use clap::Parser;
+use nom::{
+    IResult, Parser as _, bytes::complete::tag, character::complete::alphanumeric1,
+    multi::separated_list1, sequence::separated_pair,
+};
+
+#[derive(Default)]
+struct Reactor {
+    outputs: HashMap<String, Vec<String>>,
+}
+
+impl Reactor {
+    fn add_device(&mut self, name: &str, outputs: Vec<&str>) {
+        self.outputs.insert(
+            name.to_string(),
+            outputs.into_iter().map(String::from).collect(),
+        );
+    }
+
+    /// Counts the distinct paths from `from` to `to`, failing if a cycle is
+    /// reachable or the count does not fit in a `u64`.
+    ///
+    /// Walks depth first with an explicit stack, so long chains of devices
+    /// cannot overflow the call stack. `memo` holds `None` for the devices on
+    /// the stack, so meeting one of them again means the path has looped.
+    fn count_paths(&self, from: &str, to: &str) -> Result<u64, ReactorError> {
+        if from == to {
+            return Ok(1);
+        }
+        let mut memo = HashMap::from([(from, None)]);
+        // Each device being counted, with the index of its next output and the
+        // paths found through the outputs before it
+        let mut stack = vec![(from, 0, 0u64)];
+
+        while let Some(&(device, next, count)) = stack.last() {
+            let top = stack.len() - 1;
+            match self
+                .outputs
+                .get(device)
+                .and_then(|outputs| outputs.get(next))
+            {
+                Some(output) => {
+                    stack[top].1 += 1;
+                    let paths = if output == to {
+                        1
+                    } else {
+                        match memo.get(output.as_str()) {
+                            Some(&Some(paths)) => paths,
+                            Some(None) => return Err(ReactorError::Cycle(output.clone())),
+                            None => {
+                                memo.insert(output, None);
+                                stack.push((output, 0, 0));
+                                continue;
+                            }
+                        }
+                    };
+                    stack[top].2 = count.checked_add(paths).ok_or(ReactorError::Overflow)?;
+                }
+                None => {
+                    stack.pop();
+                    memo.insert(device, Some(count));
+                    match stack.last_mut() {
+                        Some((_, _, parent_count)) => {
+                            *parent_count = parent_count
+                                .checked_add(count)
+                                .ok_or(ReactorError::Overflow)?;
+                        }
+                        None => return Ok(count),
+                    }
+                }
+            }
+        }
+        unreachable!("the walk returns once the stack is empty")
+    }
+
+    /// Counts the paths from `from` to `to` that pass through both `a` and
+    /// `b`, in either order
+    fn count_paths_via(&self, from: &str, a: &str, b: &str, to: &str) -> Result<u64, ReactorError> {
+        let via = |first: &str, second: &str| -> Result<u64, ReactorError> {
+            let legs = [
+                self.count_paths(from, first)?,
+                self.count_paths(first, second)?,
+                self.count_paths(second, to)?,
+            ];
+            legs.into_iter()
+                .try_fold(1u64, u64::checked_mul)
+                .ok_or(ReactorError::Overflow)
+        };
+        via(a, b)?
+            .checked_add(via(b, a)?)
+            .ok_or(ReactorError::Overflow)
+    }
+}
+
+fn parse_device(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
+    separated_pair(
+        alphanumeric1,
+        tag(": "),
+        separated_list1(tag(" "), alphanumeric1),
+    )
+    .parse(input)
+}
+
+enum ReactorError {
+    InvalidDevice { line: usize },
+    Cycle(String),
+    Overflow,
+}
+
+impl fmt::Display for ReactorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReactorError::InvalidDevice { line } => {
+                write!(f, "Could not parse device on line {}", line)
+            }
+            ReactorError::Cycle(device) => write!(f, "Device {} is part of a cycle", device),
+            ReactorError::Overflow => write!(f, "Too many paths to count"),
+        }
+    }
+}
+
+/// The number of paths from `you` to `out`, and from `svr` to `out` through
+/// both `dac` and `fft`
+fn solve(input: &str) -> Result<(u64, u64), ReactorError> {
+    let mut reactor = Reactor::default();
+    for (line_num, line) in input.lines().enumerate() {
+        match parse_device(line) {
+            Ok(("", (name, outputs))) => reactor.add_device(name, outputs),
+            _ => return Err(ReactorError::InvalidDevice { line: line_num + 1 }),
+        }
+    }
+
+    Ok((
+        reactor.count_paths("you", "out")?,
+        reactor.count_paths_via("svr", "dac", "fft", "out")?,
+    ))
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let input = args.load_input(11).expect("Could not load input");
+
+    let (from_you, via_dac_and_fft) = solve(&input).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    println!("Paths from you: {}", from_you);
+    println!("Paths from svr via dac and fft: {}", via_dac_and_fft);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn part1_example() {
+        let input = "aaa: you hhh\nyou: bbb ccc\nbbb: ddd eee\nccc: ddd eee fff\nddd: ggg\n\
+                     eee: out\nfff: out\nggg: out\nhhh: ccc fff iii\niii: out\n";
+        assert_eq!(solve(input).ok(), Some((5, 0)));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = "svr: aaa bbb\naaa: fft\nfft: ccc\nbbb: tty\ntty: ccc\nccc: ddd eee\n\
+                     ddd: hub\nhub: fff\neee: dac\ndac: fff\nfff: ggg hhh\nggg: out\nhhh: out\n";
+        assert_eq!(solve(input).ok(), Some((0, 2)));
+    }
+
+    #[test]
+    fn rejects_cycles() {
+        assert!(matches!(
+            solve("you: aaa\naaa: you\n"),
+            Err(ReactorError::Cycle(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_too_many_paths() {
+        // Each diamond doubles the number of paths
+        let mut input = String::from("you: n0\n");
+        for i in 0..70 {
+            input += &format!("n{i}: a{i} b{i}\na{i}: n{}\nb{i}: n{}\n", i + 1, i + 1);
+        }
+        input += "n70: out\n";
+        assert!(matches!(solve(&input), Err(ReactorError::Overflow)));
+    }
+
+    #[test]
+    fn long_chains_do_not_overflow_the_stack() {
+        let mut input = String::from("you: n0\n");
+        for i in 0..300_000 {
+            input += &format!("n{i}: n{}\n", i + 1);
+        }
+        input += "n300000: out\n";
+        assert_eq!(solve(&input).ok(), Some((1, 0)));
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        let options = generate::Day11Options {
+            devices: 50,
+            max_outputs: 3,
+        };
+        for seed in 0..5 {
+            let input = generate::day11(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            assert!(solve(&input).is_ok(), "seed {}", seed);
+        }
+    }
+}
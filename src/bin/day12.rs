@@ -0,0 +1,458 @@
+use std::{fmt, process};
+
+use advent_of_code_2025::Args;
+use clap::Parser;
+use nom::{
+    IResult, Parser as _,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{line_ending, multispace0, multispace1, space1, usize},
+    combinator::{eof, map, value},
+    multi::{many1, separated_list1},
+    sequence::{preceded, separated_pair, terminated},
+};
+
+/// The cells a present covers, as `(x, y)` offsets from its top left corner
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Shape {
+    cells: Vec<(usize, usize)>,
+}
+
+impl Shape {
+    fn from_rows(rows: &[Vec<bool>]) -> Shape {
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, filled)| **filled)
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+        Shape::normalized(cells)
+    }
+
+    /// Shifts the cells so the shape touches both axes and sorts them
+    fn normalized(mut cells: Vec<(usize, usize)>) -> Shape {
+        let min_x = cells.iter().map(|c| c.0).min().unwrap_or(0);
+        let min_y = cells.iter().map(|c| c.1).min().unwrap_or(0);
+        for cell in &mut cells {
+            cell.0 -= min_x;
+            cell.1 -= min_y;
+        }
+        cells.sort_unstable();
+        Shape { cells }
+    }
+
+    fn width(&self) -> usize {
+        self.cells.iter().map(|c| c.0 + 1).max().unwrap_or(0)
+    }
+
+    fn height(&self) -> usize {
+        self.cells.iter().map(|c| c.1 + 1).max().unwrap_or(0)
+    }
+
+    /// Every distinct way the shape can be rotated and flipped
+    fn orientations(&self) -> Vec<Shape> {
+        let mut orientations = Vec::new();
+        let mut shape = self.clone();
+        for _ in 0..4 {
+            let size = shape.width().max(shape.height());
+            let flipped = shape
+                .cells
+                .iter()
+                .map(|&(x, y)| (size - 1 - x, y))
+                .collect();
+            orientations.push(Shape::normalized(flipped));
+            let rotated = shape
+                .cells
+                .iter()
+                .map(|&(x, y)| (size - 1 - y, x))
+                .collect();
+            orientations.push(shape);
+            shape = Shape::normalized(rotated);
+        }
+        orientations.sort();
+        orientations.dedup();
+        orientations
+    }
+}
+
+struct Region {
+    width: usize,
+    height: usize,
+    /// How many of each shape must fit under the tree
+    counts: Vec<usize>,
+}
+
+/// The largest region the backtracking search is tried on
+const MAX_SEARCH_CELLS: usize = 4096;
+
+/// How many cells the backtracking search may fill before giving up on a
+/// region. Exact packing is exponential, so without a limit a single tight
+/// region can run for hours.
+const SEARCH_BUDGET: usize = 1_000_000;
+
+/// Why the quick checks and the search could not tell whether a region fits
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Undecided {
+    /// The region has more than [`MAX_SEARCH_CELLS`] cells
+    TooLarge,
+    /// The search filled its budget of cells without an answer
+    SearchExhausted,
+}
+
+impl fmt::Display for Undecided {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Undecided::TooLarge => write!(f, "too large to search for a packing"),
+            Undecided::SearchExhausted => write!(f, "search ran out of budget"),
+        }
+    }
+}
+
+impl Region {
+    /// Whether every present fits in the region, or why that could not be
+    /// decided within `budget` filled cells
+    fn fits(&self, shapes: &[Shape], mut budget: usize) -> Result<bool, Undecided> {
+        let area = self
+            .width
+            .checked_mul(self.height)
+            .ok_or(Undecided::TooLarge)?;
+        let mut needed = 0usize;
+        let mut presents = 0;
+        for (shape, &count) in shapes.iter().zip(&self.counts) {
+            match count
+                .checked_mul(shape.cells.len())
+                .and_then(|cells| needed.checked_add(cells))
+            {
+                Some(cells) if cells <= area => needed = cells,
+                _ => return Ok(false),
+            }
+            // Shapes have at least one cell, so this is at most `needed`
+            presents += count;
+        }
+
+        // If every present gets its own bounding square there is no packing to do
+        let block = shapes
+            .iter()
+            .map(|s| s.width().max(s.height()))
+            .max()
+            .unwrap_or(1);
+        if (self.width / block) * (self.height / block) >= presents {
+            return Ok(true);
+        }
+        if area > MAX_SEARCH_CELLS {
+            return Err(Undecided::TooLarge);
+        }
+
+        // Every shape can be flipped, so the region can be searched on its
+        // side; filling the narrower way keeps the frontier short
+        let region = Region {
+            width: self.width.min(self.height),
+            height: self.width.max(self.height),
+            counts: self.counts.clone(),
+        };
+        let orientations: Vec<Vec<Shape>> = shapes.iter().map(Shape::orientations).collect();
+        let mut counts = self.counts.clone();
+        let mut grid = vec![vec![false; region.width]; region.height];
+        let slack = area - needed;
+        region
+            .help_fits(&orientations, &mut counts, &mut grid, 0, slack, &mut budget)
+            .ok_or(Undecided::SearchExhausted)
+    }
+
+    /// Fills the grid in reading order: the first free cell is either covered
+    /// by the top left cell of some remaining present, or left empty if there
+    /// is still `slack` to spare. Returns `None` once `budget` cells are used.
+    fn help_fits(
+        &self,
+        orientations: &[Vec<Shape>],
+        counts: &mut [usize],
+        grid: &mut [Vec<bool>],
+        pos: usize,
+        slack: usize,
+        budget: &mut usize,
+    ) -> Option<bool> {
+        if counts.iter().all(|&count| count == 0) {
+            return Some(true);
+        }
+        *budget = budget.checked_sub(1)?;
+        let Some(pos) =
+            (pos..self.width * self.height).find(|&pos| !grid[pos / self.width][pos % self.width])
+        else {
+            return Some(false);
+        };
+        let (px, py) = (pos % self.width, pos / self.width);
+
+        for present in 0..counts.len() {
+            if counts[present] == 0 {
+                continue;
+            }
+            for shape in &orientations[present] {
+                let &(ax, ay) = shape
+                    .cells
+                    .iter()
+                    .min_by_key(|&&(x, y)| (y, x))
+                    .expect("shapes are not empty");
+                let (Some(x), Some(y)) = (px.checked_sub(ax), py.checked_sub(ay)) else {
+                    continue;
+                };
+                if x + shape.width() > self.width
+                    || y + shape.height() > self.height
+                    || shape.cells.iter().any(|&(dx, dy)| grid[y + dy][x + dx])
+                {
+                    continue;
+                }
+
+                for &(dx, dy) in &shape.cells {
+                    grid[y + dy][x + dx] = true;
+                }
+                counts[present] -= 1;
+                let fits = self.help_fits(orientations, counts, grid, pos + 1, slack, budget);
+                counts[present] += 1;
+                for &(dx, dy) in &shape.cells {
+                    grid[y + dy][x + dx] = false;
+                }
+                if fits != Some(false) {
+                    return fits;
+                }
+            }
+        }
+
+        if slack > 0 {
+            grid[py][px] = true;
+            let fits = self.help_fits(orientations, counts, grid, pos + 1, slack - 1, budget);
+            grid[py][px] = false;
+            return fits;
+        }
+
+        Some(false)
+    }
+}
+
+fn parse_shape(input: &str) -> IResult<&str, Shape> {
+    map(
+        preceded(
+            (usize, tag(":"), line_ending),
+            separated_list1(
+                line_ending,
+                many1(alt((value(true, tag("#")), value(false, tag("."))))),
+            ),
+        ),
+        |rows| Shape::from_rows(&rows),
+    )
+    .parse(input)
+}
+
+fn parse_region(input: &str) -> IResult<&str, Region> {
+    map(
+        separated_pair(
+            separated_pair(usize, tag("x"), usize),
+            tag(": "),
+            separated_list1(space1, usize),
+        ),
+        |((width, height), counts)| Region {
+            width,
+            height,
+            counts,
+        },
+    )
+    .parse(input)
+}
+
+fn parse_farm(input: &str) -> IResult<&str, (Vec<Shape>, Vec<Region>)> {
+    terminated(
+        separated_pair(
+            separated_list1(multispace1, parse_shape),
+            multispace1,
+            separated_list1(line_ending, parse_region),
+        ),
+        (multispace0, eof),
+    )
+    .parse(input)
+}
+
+enum FarmError {
+    InvalidFarm { line: usize },
+    EmptyShape { shape: usize },
+    UnknownShape { region: usize, shapes: usize },
+}
+
+impl fmt::Display for FarmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FarmError::InvalidFarm { line } => write!(f, "Could not parse farm at line {}", line),
+            FarmError::EmptyShape { shape } => write!(f, "Shape {} has no cells", shape),
+            FarmError::UnknownShape { region, shapes } => write!(
+                f,
+                "Region {} lists presents for more than the {} shapes",
+                region, shapes
+            ),
+        }
+    }
+}
+
+struct Solution {
+    /// How many regions every one of their presents fits in
+    fitting: usize,
+    /// The regions, numbered from 1, that could not be decided either way
+    undecided: Vec<(usize, Undecided)>,
+}
+
+/// Counts the regions that every one of their presents fits in, searching
+/// each for at most `budget` filled cells
+fn solve(input: &str, budget: usize) -> Result<Solution, FarmError> {
+    let (shapes, regions) = match parse_farm(input) {
+        Ok((_, farm)) => farm,
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let parsed = &input[..input.len() - e.input.len()];
+            return Err(FarmError::InvalidFarm {
+                line: parsed.matches('\n').count() + 1,
+            });
+        }
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never need more input"),
+    };
+    if let Some(shape) = shapes.iter().position(|shape| shape.cells.is_empty()) {
+        return Err(FarmError::EmptyShape { shape });
+    }
+
+    let mut fitting = 0;
+    let mut undecided = Vec::new();
+    for (i, region) in regions.iter().enumerate() {
+        if region.counts.len() > shapes.len() {
+            return Err(FarmError::UnknownShape {
+                region: i + 1,
+                shapes: shapes.len(),
+            });
+        }
+        match region.fits(&shapes, budget) {
+            Ok(true) => fitting += 1,
+            Ok(false) => {}
+            Err(reason) => undecided.push((i + 1, reason)),
+        }
+    }
+
+    Ok(Solution { fitting, undecided })
+}
+
+#[derive(Parser)]
+#[command(about)]
+struct Day12Args {
+    #[command(flatten)]
+    args: Args,
+
+    /// how many cells the packing search may fill in each region before giving up
+    #[arg(long, default_value_t = SEARCH_BUDGET)]
+    search_budget: usize,
+}
+
+fn main() {
+    let args = Day12Args::parse();
+
+    let farm_str = args.args.load_input(12).expect("Could not load input");
+    let solution = solve(&farm_str, args.search_budget).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    println!("Regions that fit their presents: {}", solution.fitting);
+    if !solution.undecided.is_empty() {
+        println!("Undecided regions: {}", solution.undecided.len());
+        for (region, reason) in &solution.undecided {
+            println!("  Region {}: {}", region, reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn rejects_empty_shapes() {
+        assert!(matches!(
+            solve("0:\n...\n\n1x1: 1\n", SEARCH_BUDGET),
+            Err(FarmError::EmptyShape { shape: 0 })
+        ));
+    }
+
+    #[test]
+    fn rejects_presents_of_unknown_shapes() {
+        assert!(matches!(
+            solve("0:\n#\n\n1x1: 0 1\n", SEARCH_BUDGET),
+            Err(FarmError::UnknownShape { region: 1, .. })
+        ));
+    }
+
+    const EXAMPLE: &str = "0:\n###\n##.\n##.\n\n1:\n###\n##.\n.##\n\n2:\n.##\n###\n##.\n\n\
+                           3:\n##.\n###\n##.\n\n4:\n###\n#..\n###\n\n5:\n###\n.#.\n###\n\n\
+                           4x4: 0 0 0 0 2 0\n12x5: 1 0 1 0 2 2\n12x5: 1 0 1 0 3 2\n";
+
+    #[test]
+    fn example() {
+        let Ok(solution) = solve(EXAMPLE, SEARCH_BUDGET) else {
+            panic!("the example is valid");
+        };
+        assert_eq!(solution.fitting, 2);
+        assert_eq!(solution.undecided, []);
+    }
+
+    #[test]
+    fn search_gives_up_when_out_of_budget() {
+        let Ok((_, (shapes, regions))) = parse_farm(EXAMPLE) else {
+            panic!("the example parses");
+        };
+        // The presents do not fit, which takes an exhaustive search to show
+        assert_eq!(regions[2].fits(&shapes, SEARCH_BUDGET), Ok(false));
+        assert_eq!(
+            regions[2].fits(&shapes, 100),
+            Err(Undecided::SearchExhausted)
+        );
+    }
+
+    #[test]
+    fn undecided_regions_are_reported_without_aborting() {
+        let Ok(solution) = solve(EXAMPLE, 100) else {
+            panic!("the example is valid");
+        };
+        assert_eq!(solution.fitting, 2);
+        assert_eq!(solution.undecided, [(3, Undecided::SearchExhausted)]);
+    }
+
+    #[test]
+    fn large_regions_are_not_searched() {
+        // 600 presents of 7 cells each fill most of the 70x70 region but get
+        // only 529 bounding squares, so only a search could decide it
+        let farm = "0:\n###\n##.\n##.\n\n70x70: 600\n";
+        let Ok(solution) = solve(farm, SEARCH_BUDGET) else {
+            panic!("the farm is valid");
+        };
+        assert_eq!(solution.fitting, 0);
+        assert_eq!(solution.undecided, [(1, Undecided::TooLarge)]);
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        for hard in [false, true] {
+            let options = generate::Day12Options {
+                shapes: 4,
+                regions: 20,
+                max_size: 12,
+                hard,
+            };
+            for seed in 0..5 {
+                let input = generate::day12(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+                assert!(
+                    solve(&input, 10_000).is_ok(),
+                    "hard {}, seed {}",
+                    hard,
+                    seed
+                );
+            }
+        }
+    }
+}
@@ -1,42 +1,44 @@
 use advent_of_code_2025::Args;
 use clap::Parser;
 use nom::{
-    IResult, Parser as _, bytes::complete::tag, character::complete::u64, multi::separated_list1,
-    sequence::separated_pair,
+    IResult, Parser as _,
+    bytes::complete::tag,
+    character::complete::{multispace0, u64},
+    combinator::eof,
+    multi::separated_list1,
+    sequence::{separated_pair, terminated},
 };
+use std::{fmt, process};
 
-use std::{
-    fs::File,
-    io::{BufReader, Read},
-};
+#[derive(Parser)]
+#[command(about)]
+struct Day2Args {
+    #[command(flatten)]
+    args: Args,
 
-fn parse_range(input: &str) -> IResult<&str, (u64, u64)> {
-    separated_pair(u64, tag("-"), u64).parse(input)
+    /// check every ID one at a time instead of summing them arithmetically
+    #[arg(long)]
+    naive: bool,
 }
 
-fn parse_ranges(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
-    separated_list1(tag(","), parse_range).parse(input)
+/// The sums of the IDs made of a digit sequence repeated exactly twice, and of
+/// the IDs made of a digit sequence repeated two or more times
+#[derive(Debug, PartialEq, Eq)]
+struct Sums {
+    repeat: u128,
+    multi: u128,
 }
 
-fn main() {
-    let args = Args::parse();
-
-    let input_file = File::open(args.input).expect("Could not find file");
-    let mut reader = BufReader::new(input_file);
-
-    let mut range_str = String::new();
-    reader
-        .read_to_string(&mut range_str)
-        .expect("Could not read file");
-    let (_, ranges) = parse_ranges(&range_str).expect("Could not parse ranges");
-
+/// The reference solver: converts every ID in every range to a string and
+/// compares its pieces
+fn naive_sums(ranges: &[(u64, u64)]) -> Sums {
     let mut repeat_sum = 0;
     let mut multi_sum = 0;
-    for (start, end) in ranges {
+    for &(start, end) in ranges {
         for i in start..=end {
             let num_str = i.to_string();
             if num_str[..num_str.len() / 2] == num_str[num_str.len() / 2..] {
-                repeat_sum += i;
+                repeat_sum += i as u128;
             }
 
             let mut multi = false;
@@ -56,11 +58,192 @@ fn main() {
                 }
             }
             if multi {
-                multi_sum += i;
+                multi_sum += i as u128;
             }
         }
     }
 
-    println!("Repeat sum: {}", repeat_sum);
-    println!("Multi sum: {}", multi_sum);
+    Sums {
+        repeat: repeat_sum,
+        multi: multi_sum,
+    }
+}
+
+/// The most digits a `u64` can have
+const MAX_LEN: u32 = 20;
+
+/// Sums the `len` digit IDs in `start..=end` that repeat a `period` digit block.
+///
+/// Those IDs are exactly the `period` digit blocks multiplied by `1` followed
+/// by `len / period - 1` copies of `period - 1` zeros and a one, e.g.
+/// `1010101` for a block of 2 repeated 4 times, so they form an arithmetic
+/// series.
+fn periodic_sum(start: u64, end: u64, len: u32, period: u32) -> u128 {
+    let step = (10u128.pow(len) - 1) / (10u128.pow(period) - 1);
+    let lo = 10u128.pow(period - 1).max((start as u128).div_ceil(step));
+    let hi = (10u128.pow(period) - 1).min(end as u128 / step);
+    if lo > hi {
+        return 0;
+    }
+    step * (lo + hi) * (hi - lo + 1) / 2
+}
+
+/// Sums the invalid IDs without visiting them, so a range costs the same
+/// whatever its width.
+///
+/// An ID repeating a block of `d` digits also repeats every block whose length
+/// is a multiple of `d` and divides its length, so the IDs of length `len` made
+/// of repeated blocks are those repeating a block of `len / p` digits for some
+/// prime `p` dividing `len`. Overlaps between primes are handled by
+/// inclusion-exclusion.
+fn sums(ranges: &[(u64, u64)]) -> Sums {
+    let mut total = Sums {
+        repeat: 0,
+        multi: 0,
+    };
+    for &(start, end) in ranges {
+        for len in 2..=MAX_LEN {
+            if len % 2 == 0 {
+                total.repeat += periodic_sum(start, end, len, len / 2);
+            }
+
+            let primes: Vec<u32> = (2..=len)
+                .filter(|&p| len % p == 0 && (2..p).all(|q| p % q != 0))
+                .collect();
+            let (mut added, mut removed) = (0, 0);
+            for subset in 1..1u32 << primes.len() {
+                let product: u32 = (0..primes.len())
+                    .filter(|&i| subset & (1 << i) != 0)
+                    .map(|i| primes[i])
+                    .product();
+                let sum = periodic_sum(start, end, len, len / product);
+                if subset.count_ones() % 2 == 1 {
+                    added += sum;
+                } else {
+                    removed += sum;
+                }
+            }
+            total.multi += added - removed;
+        }
+    }
+
+    total
+}
+
+fn parse_range(input: &str) -> IResult<&str, (u64, u64)> {
+    separated_pair(u64, tag("-"), u64).parse(input)
+}
+
+fn parse_ranges(input: &str) -> IResult<&str, Vec<(u64, u64)>> {
+    terminated(separated_list1(tag(","), parse_range), (multispace0, eof)).parse(input)
+}
+
+/// Where the ranges stopped parsing, both 1-based
+#[derive(Debug)]
+struct ParseError {
+    line: usize,
+    column: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Could not parse ranges at line {}, column {}",
+            self.line, self.column
+        )
+    }
+}
+
+fn solve(input: &str, naive: bool) -> Result<Sums, ParseError> {
+    let ranges = match parse_ranges(input) {
+        Ok((_, ranges)) => ranges,
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            let parsed = &input[..input.len() - e.input.len()];
+            let line_start = parsed.rfind('\n').map_or(0, |i| i + 1);
+            return Err(ParseError {
+                line: parsed.matches('\n').count() + 1,
+                column: parsed.len() - line_start + 1,
+            });
+        }
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never need more input"),
+    };
+
+    Ok(if naive {
+        naive_sums(&ranges)
+    } else {
+        sums(&ranges)
+    })
+}
+
+fn main() {
+    let Day2Args { args, naive } = Day2Args::parse();
+
+    let range_str = args.load_input(2).expect("Could not load input");
+    let sums = solve(&range_str, naive).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+
+    println!("Repeat sum: {}", sums.repeat);
+    println!("Multi sum: {}", sums.multi);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use proptest::prelude::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Ranges of up to a few thousand IDs around an ID made of a repeated
+    /// block, so most ranges contain invalid IDs
+    fn range_near_repeat() -> impl Strategy<Value = (u64, u64)> {
+        (1..100_000u64, 2..=6usize, 0..3000u64, 0..3000u64).prop_filter_map(
+            "repeated block overflows",
+            |(block, repeats, before, after)| {
+                let id: u64 = block.to_string().repeat(repeats).parse().ok()?;
+                Some((id.saturating_sub(before), id.checked_add(after)?))
+            },
+        )
+    }
+
+    /// Ranges of up to a few thousand IDs starting anywhere
+    fn range_anywhere() -> impl Strategy<Value = (u64, u64)> {
+        (1..=MAX_LEN, any::<u64>(), 0..3000u64).prop_map(|(len, start, width)| {
+            let start = start % 10u64.saturating_pow(len).min(u64::MAX - width);
+            (start, start + width)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn sums_match_naive(
+            ranges in prop::collection::vec(prop_oneof![range_near_repeat(), range_anywhere()], 1..4),
+        ) {
+            prop_assert_eq!(sums(&ranges), naive_sums(&ranges));
+        }
+    }
+
+    #[test]
+    fn rejects_unparsable_ranges() {
+        assert!(solve("x\n", false).is_err());
+    }
+
+    #[test]
+    fn naive_and_fast_agree_on_generated_inputs() {
+        let options = generate::Day2Options {
+            ranges: 10,
+            max_width: 1000,
+            max_digits: 10,
+        };
+        for seed in 0..5 {
+            let input = generate::day2(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            let (Ok(naive), Ok(fast)) = (solve(&input, true), solve(&input, false)) else {
+                panic!("seed {} does not parse", seed);
+            };
+            assert_eq!(naive, fast, "seed {}", seed);
+        }
+    }
 }
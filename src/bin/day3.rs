@@ -1,68 +1,347 @@
-use advent_of_code_2025::Args;
-use clap::Parser;
-use nom::{IResult, Parser as _, bytes::complete::take, combinator::map, multi::many1};
-
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+use advent_of_code_2025::{
+    Args,
+    joltage::{Joltage, MAX_DIGITS, max_joltage},
+};
+use clap::{Parser, ValueEnum};
+use nom::{
+    IResult, Parser as _,
+    character::complete::satisfy,
+    combinator::{eof, map},
+    multi::many1,
+    sequence::terminated,
 };
 
-fn parse_battery_bank(input: &str) -> IResult<&str, Vec<u32>> {
-    many1(map(take(1usize), |d: &str| d.parse::<u32>().unwrap())).parse(input)
+use std::{fmt, io::IsTerminal, process};
+
+#[derive(Parser)]
+#[command(about)]
+struct Day3Args {
+    #[command(flatten)]
+    args: Args,
+
+    /// how many batteries to turn on in each bank
+    #[arg(
+        short,
+        long = "digits",
+        value_delimiter = ',',
+        default_values_t = [2, 12],
+        value_parser = clap::value_parser!(u8).range(1..=MAX_DIGITS as i64),
+    )]
+    n: Vec<u8>,
+
+    /// print every bank with its chosen batteries highlighted
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// how to highlight chosen batteries in verbose output
+    #[arg(long, value_enum, default_value_t = Highlight::Auto)]
+    highlight: Highlight,
 }
 
-fn find_two_digit_joltage(bank: &Vec<u32>) -> u32 {
-    let mut first = 0;
-    let mut second = 0;
+#[derive(Clone, Copy, ValueEnum)]
+enum Highlight {
+    /// colour when writing to a terminal, brackets otherwise
+    Auto,
+    Color,
+    Brackets,
+}
+
+impl Highlight {
+    fn resolve(self) -> Highlight {
+        match self {
+            Highlight::Auto if std::io::stdout().is_terminal() => Highlight::Color,
+            Highlight::Auto => Highlight::Brackets,
+            other => other,
+        }
+    }
+}
+
+fn render_bank(bank: &[u32], joltage: &Joltage, highlight: Highlight) -> String {
+    let mut chosen = joltage.indices.iter().peekable();
+    let mut rendered = String::new();
     for (i, battery) in bank.iter().enumerate() {
-        if *battery > first && i != bank.len() - 1 {
-            first = *battery;
-            second = 0;
-        } else if *battery > second {
-            second = *battery;
+        if chosen.next_if_eq(&&i).is_some() {
+            match highlight {
+                Highlight::Color => rendered.push_str(&format!("\x1b[1;32m{}\x1b[0m", battery)),
+                _ => rendered.push_str(&format!("[{}]", battery)),
+            }
+        } else {
+            rendered.push_str(&battery.to_string());
         }
     }
+    rendered
+}
+
+fn parse_battery(input: &str) -> IResult<&str, u32> {
+    map(satisfy(|c| c.is_ascii_digit()), |d| {
+        d.to_digit(10).expect("ASCII digits are base 10")
+    })
+    .parse(input)
+}
+
+fn parse_battery_bank(input: &str) -> IResult<&str, Vec<u32>> {
+    terminated(many1(parse_battery), eof).parse(input)
+}
+
+#[derive(Debug, PartialEq)]
+enum BankError {
+    InvalidBattery {
+        line: usize,
+        column: usize,
+        battery: char,
+    },
+    Empty {
+        line: usize,
+    },
+    TooShort {
+        line: usize,
+        batteries: usize,
+        digits: u8,
+    },
+    Overflow {
+        digits: u8,
+    },
+}
+
+impl fmt::Display for BankError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BankError::InvalidBattery {
+                line,
+                column,
+                battery,
+            } => write!(
+                f,
+                "Invalid battery {:?} on line {}, column {}",
+                battery, line, column
+            ),
+            BankError::Empty { line } => write!(f, "Empty battery bank on line {}", line),
+            BankError::TooShort {
+                line,
+                batteries,
+                digits,
+            } => write!(
+                f,
+                "Bank on line {} has {} batteries but {} were requested",
+                line, batteries, digits
+            ),
+            BankError::Overflow { digits } => {
+                write!(f, "Total {} digit joltage overflowed", digits)
+            }
+        }
+    }
+}
+
+/// A bank and its best joltage for each requested number of digits
+struct BankJoltages {
+    bank: Vec<u32>,
+    joltages: Vec<Joltage>,
+}
 
-    first * 10 + second
+struct Solution {
+    banks: Vec<BankJoltages>,
+    /// The total joltage for each requested number of digits
+    totals: Vec<u128>,
 }
 
-fn find_n_digit_joltage(bank: &Vec<u32>, n: usize) -> u64 {
-    let mut digits = vec![0; n];
-    let mut last_taken_idx = 0;
-    for (i, e) in digits.iter_mut().enumerate() {
-        let mut biggest = 0;
-        let start = if i == 0 { i } else { last_taken_idx + 1 };
-        for j in start..(bank.len() - (n - i - 1)) {
-            if bank[j] > biggest {
-                biggest = bank[j];
-                last_taken_idx = j;
+/// Finds the best joltage of every bank for each of `n` digits, or returns
+/// every problem found with the banks
+fn solve(input: &str, n: &[u8]) -> Result<Solution, Vec<BankError>> {
+    let mut solution = Solution {
+        banks: Vec::new(),
+        totals: vec![0; n.len()],
+    };
+    let mut errors = Vec::new();
+    for (line_num, line) in input.lines().enumerate() {
+        let line_num = line_num + 1;
+        let bank = match parse_battery_bank(line) {
+            Ok((_, bank)) => bank,
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+                let parsed = &line[..line.len() - e.input.len()];
+                errors.push(match e.input.chars().next() {
+                    Some(battery) => BankError::InvalidBattery {
+                        line: line_num,
+                        column: parsed.chars().count() + 1,
+                        battery,
+                    },
+                    None => BankError::Empty { line: line_num },
+                });
+                continue;
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never need more input"),
+        };
+
+        let mut joltages = Vec::with_capacity(n.len());
+        for &digits in n {
+            match max_joltage(&bank, digits as usize) {
+                Some(joltage) => joltages.push(joltage),
+                None => errors.push(BankError::TooShort {
+                    line: line_num,
+                    batteries: bank.len(),
+                    digits,
+                }),
             }
         }
-        *e = biggest;
+        if errors.is_empty() {
+            solution.banks.push(BankJoltages { bank, joltages });
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
     }
 
-    let mut joltage = 0;
-    for (i, digit) in digits.iter().enumerate() {
-        joltage += (10u64.pow((n - i - 1) as u32)) * (*digit as u64);
+    for (i, &digits) in n.iter().enumerate() {
+        for bank in &solution.banks {
+            solution.totals[i] = solution.totals[i]
+                .checked_add(bank.joltages[i].value)
+                .ok_or(vec![BankError::Overflow { digits }])?;
+        }
     }
-    joltage
+    Ok(solution)
 }
 
 fn main() {
-    let args = Args::parse();
-
-    let input_file = File::open(args.input).expect("Could not find file");
-    let reader = BufReader::new(input_file);
-
-    let mut two_digit_joltage = 0;
-    let mut twelve_digit_joltage = 0;
-    for line in reader.lines() {
-        let line = line.expect("Could not read line");
-        let (_, bank) = parse_battery_bank(&line).expect("Could not parse line");
-        two_digit_joltage += find_two_digit_joltage(&bank);
-        twelve_digit_joltage += find_n_digit_joltage(&bank, 12);
+    let Day3Args {
+        args,
+        n,
+        verbose,
+        highlight,
+    } = Day3Args::parse();
+    let highlight = highlight.resolve();
+
+    let input = args.load_input(3).expect("Could not load input");
+
+    let solution = solve(&input, &n).unwrap_or_else(|errors| {
+        for e in errors {
+            eprintln!("{}", e);
+        }
+        process::exit(1);
+    });
+
+    if verbose {
+        for (i, BankJoltages { bank, joltages }) in solution.banks.iter().enumerate() {
+            println!("Bank {}:", i + 1);
+            for (digits, joltage) in n.iter().zip(joltages) {
+                println!(
+                    "{:>4}: {} = {}",
+                    digits,
+                    render_bank(bank, joltage, highlight),
+                    joltage.value
+                );
+            }
+        }
     }
 
-    println!("two digit joltage: {}", two_digit_joltage);
-    println!("twelve digit joltage: {}", twelve_digit_joltage);
+    for (digits, total) in n.iter().zip(solution.totals) {
+        println!("{} digit joltage: {}", digits, total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn reports_invalid_batteries() {
+        let Err(errors) = solve("12a4\n98é7\n", &[2]) else {
+            panic!("both banks hold invalid batteries");
+        };
+        assert_eq!(
+            errors,
+            [
+                BankError::InvalidBattery {
+                    line: 1,
+                    column: 3,
+                    battery: 'a'
+                },
+                BankError::InvalidBattery {
+                    line: 2,
+                    column: 3,
+                    battery: 'é'
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_empty_and_short_banks() {
+        let Err(errors) = solve("1234\n\n1\n", &[2]) else {
+            panic!("the second bank is empty and the third too short");
+        };
+        assert_eq!(
+            errors,
+            [
+                BankError::Empty { line: 2 },
+                BankError::TooShort {
+                    line: 3,
+                    batteries: 1,
+                    digits: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn collects_every_bad_line() {
+        let Err(errors) = solve("x1\n\n987\n12\n3\n", &[3]) else {
+            panic!("four of the banks are bad");
+        };
+        let lines: Vec<usize> = errors
+            .iter()
+            .map(|e| match *e {
+                BankError::InvalidBattery { line, .. }
+                | BankError::Empty { line }
+                | BankError::TooShort { line, .. } => line,
+                BankError::Overflow { .. } => 0,
+            })
+            .collect();
+        assert_eq!(lines, [1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn rejects_overflowing_totals() {
+        let input = format!("{}\n", "9".repeat(MAX_DIGITS)).repeat(4);
+        assert!(matches!(
+            solve(&input, &[MAX_DIGITS as u8])
+                .as_ref()
+                .map_err(Vec::as_slice),
+            Err([BankError::Overflow { .. }])
+        ));
+    }
+
+    #[test]
+    fn brackets_wrap_exactly_the_chosen_batteries() {
+        let bank = [8, 1, 8, 1, 8, 1, 9, 1, 1];
+        let joltage = max_joltage(&bank, 5).unwrap();
+        assert_eq!(joltage.value, 88911);
+        assert_eq!(
+            render_bank(&bank, &joltage, Highlight::Brackets),
+            "[8]1[8]181[9][1][1]"
+        );
+    }
+
+    #[test]
+    fn colour_highlights_the_chosen_batteries() {
+        let bank = [3, 3, 1];
+        let joltage = max_joltage(&bank, 1).unwrap();
+        assert_eq!(
+            render_bank(&bank, &joltage, Highlight::Color),
+            "\x1b[1;32m3\x1b[0m31"
+        );
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        let options = generate::Day3Options {
+            banks: 20,
+            batteries: 20,
+        };
+        for seed in 0..5 {
+            let input = generate::day3(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            assert!(solve(&input, &[2, 12]).is_ok(), "seed {}", seed);
+        }
+    }
 }
@@ -1,103 +1,736 @@
 use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+    collections::VecDeque,
+    fmt,
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    process,
 };
 
 use advent_of_code_2025::Args;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 
-fn is_accessible(grid: &Vec<Vec<bool>>, y: usize, x: usize) -> bool {
-    let row = &grid[y];
-    if grid[y][x] {
-        let mut empty = 0;
-        if y == 0 || x == 0 || !grid[y - 1][x - 1] {
-            empty += 1;
+#[derive(Parser)]
+#[command(about)]
+struct Day4Args {
+    #[command(flatten)]
+    args: Args,
+
+    /// how removals within a round affect each other; pass several to compare them
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [Semantics::Synchronous]
+    )]
+    semantics: Vec<Semantics>,
+
+    /// find synchronous rounds by rescanning the whole grid every round
+    /// instead of tracking empty neighbour counts
+    #[arg(long)]
+    naive: bool,
+
+    /// which cells count as a roll's neighbours
+    #[arg(long, value_enum, default_value_t = Neighbourhood::Moore)]
+    neighbourhood: Neighbourhood,
+
+    /// how far the neighbourhood reaches
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..=MAX_RADIUS))]
+    radius: u16,
+
+    /// how many neighbours must be empty for a roll to be accessible
+    #[arg(long, default_value_t = 5)]
+    min_empty: usize,
+
+    /// what lies beyond the edges of the grid
+    #[arg(long, value_enum, default_value_t = Boundary::Empty)]
+    boundary: Boundary,
+
+    /// print the grid with accessible rolls marked `x`
+    #[arg(long)]
+    show: bool,
+
+    /// write a frame for every removal round into this directory
+    #[arg(long)]
+    frames: Option<PathBuf>,
+
+    /// file format of the frames written by --frames
+    #[arg(long, value_enum, default_value_t = FrameFormat::Text)]
+    frame_format: FrameFormat,
+}
+
+/// The widest neighbourhood allowed; a rule holds every offset within the
+/// radius, so this keeps it to about 160,000 of them
+const MAX_RADIUS: i64 = 200;
+
+#[derive(Clone, Copy, ValueEnum)]
+enum FrameFormat {
+    Text,
+    /// binary PPM image, one cell per CELL_PIXELS square
+    Ppm,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Semantics {
+    /// every roll accessible at the start of a round is removed together
+    Synchronous,
+    /// rolls are removed while scanning, so later cells see earlier removals
+    Sweep,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Neighbourhood {
+    /// cells within the radius horizontally or vertically
+    VonNeumann,
+    /// cells within the radius in every direction, including diagonals
+    Moore,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum Boundary {
+    /// cells off the grid count as empty
+    Empty,
+    /// cells off the grid count as rolls
+    Occupied,
+    /// the grid wraps around at its edges
+    Wrap,
+}
+
+/// Decides which rolls a forklift can reach
+#[derive(Debug)]
+struct Rule {
+    offsets: Vec<(isize, isize)>,
+    min_empty: usize,
+    boundary: Boundary,
+}
+
+impl Rule {
+    fn new(
+        neighbourhood: Neighbourhood,
+        radius: usize,
+        min_empty: usize,
+        boundary: Boundary,
+    ) -> Rule {
+        let r = radius as isize;
+        let offsets = (-r..=r)
+            .flat_map(|dy| (-r..=r).map(move |dx| (dy, dx)))
+            .filter(|&d| d != (0, 0))
+            .filter(|&(dy, dx)| match neighbourhood {
+                Neighbourhood::VonNeumann => dy.abs() + dx.abs() <= r,
+                Neighbourhood::Moore => true,
+            })
+            .collect();
+
+        Rule {
+            offsets,
+            min_empty,
+            boundary,
         }
-        if y == 0 || !grid[y - 1][x] {
-            empty += 1;
+    }
+
+    /// Yields every neighbour of a cell, or `None` for neighbours off the grid.
+    /// The offsets are symmetric, so these are also the cells that have
+    /// `(y, x)` as a neighbour.
+    fn neighbours<'a>(
+        &'a self,
+        grid: &'a [Vec<bool>],
+        y: usize,
+        x: usize,
+    ) -> impl Iterator<Item = Option<(usize, usize)>> + 'a {
+        self.offsets
+            .iter()
+            .map(move |&(dy, dx)| match self.boundary {
+                Boundary::Wrap => {
+                    let ny = (y as isize + dy).rem_euclid(grid.len() as isize) as usize;
+                    let nx = (x as isize + dx).rem_euclid(grid[ny].len() as isize) as usize;
+                    Some((ny, nx))
+                }
+                Boundary::Empty | Boundary::Occupied => {
+                    let ny = y.checked_add_signed(dy).filter(|&ny| ny < grid.len())?;
+                    let nx = x.checked_add_signed(dx).filter(|&nx| nx < grid[ny].len())?;
+                    Some((ny, nx))
+                }
+            })
+    }
+
+    fn empty_neighbours(&self, grid: &[Vec<bool>], y: usize, x: usize) -> usize {
+        self.neighbours(grid, y, x)
+            .filter(|n| match n {
+                Some((ny, nx)) => !grid[*ny][*nx],
+                None => matches!(self.boundary, Boundary::Empty),
+            })
+            .count()
+    }
+
+    fn is_accessible(&self, grid: &[Vec<bool>], y: usize, x: usize) -> bool {
+        grid[y][x] && self.empty_neighbours(grid, y, x) >= self.min_empty
+    }
+}
+
+/// The rolls removed in a single round, in the order they were removed
+type Round = Vec<(usize, usize)>;
+
+/// Removes accessible rolls in row-major sweeps, where a removal is visible to
+/// the rest of the same sweep, until a sweep removes nothing. Returns the
+/// rolls removed in each sweep.
+fn sweep_all(grid: &[Vec<bool>], rule: &Rule) -> Vec<Round> {
+    let mut grid = grid.to_vec();
+    let mut rounds = Vec::new();
+    loop {
+        let removed = remove_accessible(&mut grid, rule);
+        if !removed.is_empty() {
+            rounds.push(removed);
+        } else {
+            break;
         }
-        if y == 0 || x == row.len() - 1 || !grid[y - 1][x + 1] {
-            empty += 1;
+    }
+
+    rounds
+}
+
+fn remove_accessible(grid: &mut [Vec<bool>], rule: &Rule) -> Round {
+    let mut removed = Vec::new();
+    for y in 0..grid.len() {
+        for x in 0..grid[y].len() {
+            if rule.is_accessible(grid, y, x) {
+                removed.push((y, x));
+                grid[y][x] = false;
+            }
         }
-        if x == 0 || !grid[y][x - 1] {
-            empty += 1;
+    }
+
+    removed
+}
+
+/// The reference solver for synchronous rounds: scans the whole grid for
+/// accessible rolls each round, then removes them all at once
+fn rescan_all(grid: &[Vec<bool>], rule: &Rule) -> Vec<Round> {
+    let mut grid = grid.to_vec();
+    let mut rounds = Vec::new();
+    loop {
+        let removed: Round = (0..grid.len())
+            .flat_map(|y| (0..grid[y].len()).map(move |x| (y, x)))
+            .filter(|&(y, x)| rule.is_accessible(&grid, y, x))
+            .collect();
+        if removed.is_empty() {
+            break;
         }
-        if x == row.len() - 1 || !grid[y][x + 1] {
-            empty += 1;
+        for &(y, x) in &removed {
+            grid[y][x] = false;
         }
-        if y == grid.len() - 1 || x == 0 || !grid[y + 1][x - 1] {
-            empty += 1;
+        rounds.push(removed);
+    }
+
+    rounds
+}
+
+/// Removes accessible rolls until none are left, returning the rolls removed
+/// in each round.
+///
+/// Every roll starts with a count of its empty neighbours, and only the
+/// neighbours of a removed roll are looked at again, so each cell is visited
+/// a constant number of times. A roll removed in round `n` makes its
+/// neighbours removable in round `n + 1` at the earliest.
+fn remove_all(grid: &[Vec<bool>], rule: &Rule) -> Vec<Round> {
+    let mut grid = grid.to_vec();
+    let mut empty: Vec<Vec<usize>> = grid
+        .iter()
+        .enumerate()
+        .map(|(y, row)| {
+            (0..row.len())
+                .map(|x| rule.empty_neighbours(&grid, y, x))
+                .collect()
+        })
+        .collect();
+
+    let mut queue = VecDeque::new();
+    for (y, row) in grid.iter().enumerate() {
+        for x in 0..row.len() {
+            if rule.is_accessible(&grid, y, x) {
+                queue.push_back((y, x, 0));
+            }
         }
-        if y == grid.len() - 1 || !grid[y + 1][x] {
-            empty += 1;
+    }
+
+    let mut rounds = Vec::new();
+    while let Some((y, x, round)) = queue.pop_front() {
+        if !grid[y][x] {
+            continue;
         }
-        if y == grid.len() - 1 || x == row.len() - 1 || !grid[y + 1][x + 1] {
-            empty += 1;
+        grid[y][x] = false;
+        if rounds.len() <= round {
+            rounds.push(Vec::new());
         }
+        rounds[round].push((y, x));
 
-        if empty >= 5 {
-            return true;
+        let neighbours: Vec<(usize, usize)> = rule.neighbours(&grid, y, x).flatten().collect();
+        for (ny, nx) in neighbours {
+            empty[ny][nx] += 1;
+            // Only queue a roll the moment it becomes accessible so it is queued once
+            if grid[ny][nx] && empty[ny][nx] == rule.min_empty {
+                queue.push_back((ny, nx, round + 1));
+            }
         }
     }
 
-    false
+    rounds
 }
 
-fn count_accessible(grid: &Vec<Vec<bool>>) -> u32 {
-    let mut count = 0;
-    for (y, row) in grid.iter().enumerate() {
-        for (x, _) in row.iter().enumerate() {
-            if is_accessible(grid, y, x) {
-                count += 1;
+/// Draws rolls as `@` and empty cells as `.`, marking the given rolls `x`
+fn render_text(grid: &[Vec<bool>], marked: &[(usize, usize)]) -> String {
+    let mut chars: Vec<Vec<char>> = grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&roll| if roll { '@' } else { '.' })
+                .collect()
+        })
+        .collect();
+    for &(y, x) in marked {
+        chars[y][x] = 'x';
+    }
+
+    chars
+        .into_iter()
+        .map(|row| row.into_iter().chain(['\n']).collect::<String>())
+        .collect()
+}
+
+/// Width and height in pixels of a single cell in PPM frames
+const CELL_PIXELS: usize = 4;
+
+fn write_ppm(path: &Path, grid: &[Vec<bool>], marked: &[(usize, usize)]) -> std::io::Result<()> {
+    const EMPTY: [u8; 3] = [240, 240, 240];
+    const ROLL: [u8; 3] = [90, 90, 90];
+    const MARKED: [u8; 3] = [220, 40, 40];
+
+    let mut colours: Vec<Vec<[u8; 3]>> = grid
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&roll| if roll { ROLL } else { EMPTY })
+                .collect()
+        })
+        .collect();
+    for &(y, x) in marked {
+        colours[y][x] = MARKED;
+    }
+
+    let width = grid.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut out = BufWriter::new(File::create(path)?);
+    write!(
+        out,
+        "P6\n{} {}\n255\n",
+        width * CELL_PIXELS,
+        grid.len() * CELL_PIXELS
+    )?;
+    for row in &colours {
+        for _ in 0..CELL_PIXELS {
+            for x in 0..width {
+                let colour = row.get(x).unwrap_or(&EMPTY);
+                for _ in 0..CELL_PIXELS {
+                    out.write_all(colour)?;
+                }
             }
         }
     }
+    out.flush()
+}
 
-    count
+/// Writes one frame per round showing the rolls about to be removed, then a
+/// final frame of the rolls that are left
+fn write_frames(
+    dir: &Path,
+    format: FrameFormat,
+    prefix: &str,
+    grid: &[Vec<bool>],
+    rounds: &[Round],
+) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut grid = grid.to_vec();
+    let no_marks = Round::new();
+    for (frame, marked) in rounds.iter().chain([&no_marks]).enumerate() {
+        let path = dir.join(format!("{}-{:04}", prefix, frame));
+        match format {
+            FrameFormat::Text => fs::write(path.with_extension("txt"), render_text(&grid, marked))?,
+            FrameFormat::Ppm => write_ppm(&path.with_extension("ppm"), &grid, marked)?,
+        }
+        for &(y, x) in marked {
+            grid[y][x] = false;
+        }
+    }
+
+    Ok(())
 }
 
-fn remove_accessible(grid: &mut Vec<Vec<bool>>) -> u32 {
-    let mut count = 0;
-    for y in 0..grid.len() {
-        let row = &grid[y];
-        for x in 0..row.len() {
-            if is_accessible(grid, y, x) {
-                count += 1;
-                grid[y][x] = false;
+enum GridError {
+    InvalidCell {
+        line: usize,
+        column: usize,
+        cell: char,
+    },
+    Ragged {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::InvalidCell { line, column, cell } => {
+                write!(
+                    f,
+                    "Invalid cell {:?} on line {}, column {}",
+                    cell, line, column
+                )
             }
+            GridError::Ragged {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "Line {} is {} cells wide but the first line is {}",
+                line, width, expected
+            ),
         }
     }
+}
 
-    count
+/// Reads a grid of `@` rolls and `.` empty cells, which must be rectangular
+fn parse_grid(input: &str) -> Result<Vec<Vec<bool>>, GridError> {
+    let mut grid: Vec<Vec<bool>> = Vec::new();
+    for (line_num, line) in input.lines().enumerate() {
+        let mut row = Vec::new();
+        for (col, c) in line.chars().enumerate() {
+            match c {
+                '@' => row.push(true),
+                '.' => row.push(false),
+                _ => {
+                    return Err(GridError::InvalidCell {
+                        line: line_num + 1,
+                        column: col + 1,
+                        cell: c,
+                    });
+                }
+            }
+        }
+        if let Some(first) = grid.first()
+            && first.len() != row.len()
+        {
+            return Err(GridError::Ragged {
+                line: line_num + 1,
+                width: row.len(),
+                expected: first.len(),
+            });
+        }
+        grid.push(row);
+    }
+
+    Ok(grid)
+}
+
+struct Solution {
+    grid: Vec<Vec<bool>>,
+    /// The rolls accessible before any are removed
+    accessible: Vec<(usize, usize)>,
+    /// The rounds of removals under each of the requested semantics
+    removals: Vec<(Semantics, Vec<Round>)>,
+}
+
+fn solve(
+    input: &str,
+    rule: &Rule,
+    semantics: &[Semantics],
+    naive: bool,
+) -> Result<Solution, GridError> {
+    let grid = parse_grid(input)?;
+    let accessible = (0..grid.len())
+        .flat_map(|y| (0..grid[y].len()).map(move |x| (y, x)))
+        .filter(|&(y, x)| rule.is_accessible(&grid, y, x))
+        .collect();
+    let removals = semantics
+        .iter()
+        .map(|&semantics| {
+            let rounds = match semantics {
+                Semantics::Synchronous if naive => rescan_all(&grid, rule),
+                Semantics::Synchronous => remove_all(&grid, rule),
+                Semantics::Sweep => sweep_all(&grid, rule),
+            };
+            (semantics, rounds)
+        })
+        .collect();
+
+    Ok(Solution {
+        grid,
+        accessible,
+        removals,
+    })
 }
 
 fn main() {
-    let args = Args::parse();
+    let Day4Args {
+        args,
+        semantics,
+        naive,
+        neighbourhood,
+        radius,
+        min_empty,
+        boundary,
+        show,
+        frames,
+        frame_format,
+    } = Day4Args::parse();
+    let rule = Rule::new(neighbourhood, radius as usize, min_empty, boundary);
 
-    let input_file = File::open(args.input).expect("Could not find file");
-    let reader = BufReader::new(input_file);
+    let input = args.load_input(4).expect("Could not load input");
 
-    let mut grid = Vec::new();
+    let Solution {
+        grid,
+        accessible,
+        removals,
+    } = solve(&input, &rule, &semantics, naive).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
 
-    for line in reader.lines() {
-        let line = line.expect("Could not read line");
-        grid.push(Vec::new());
-        for c in line.chars() {
-            grid.last_mut().unwrap().push(c == '@');
+    let roll_count = grid.iter().flatten().filter(|&&roll| roll).count() as u32;
+    println!("Accessible count: {}", accessible.len());
+    if show {
+        print!("{}", render_text(&grid, &accessible));
+    }
+
+    let mut totals = Vec::new();
+    for (semantics, rounds) in removals {
+        let total_removed: u32 = rounds.iter().map(|round| round.len() as u32).sum();
+        if let Some(dir) = &frames {
+            let prefix = format!("{:?}", semantics).to_lowercase();
+            write_frames(dir, frame_format, &prefix, &grid, &rounds)
+                .expect("Could not write frames");
         }
+
+        println!("{:?} removal:", semantics);
+        let mut remaining = roll_count;
+        for (round, removed) in rounds.iter().enumerate() {
+            let removed = removed.len() as u32;
+            remaining -= removed;
+            println!(
+                "  Round {}: removed {}, {} remaining",
+                round + 1,
+                removed,
+                remaining
+            );
+        }
+        println!("  Rounds: {}", rounds.len());
+        println!("  Total removed: {}", total_removed);
+        totals.push(total_removed);
     }
 
-    let accessible_count = count_accessible(&grid);
-    let mut total_removed = 0;
-    loop {
-        let removed = remove_accessible(&mut grid);
-        if removed > 0 {
-            total_removed += removed;
-        } else {
-            break;
+    if totals.len() > 1 {
+        let agree = totals.windows(2).all(|w| w[0] == w[1]);
+        println!("Totals agree: {}", agree);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use proptest::prelude::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const EXAMPLE: &str = "..@@.@@@@.\n@@@.@.@.@@\n@@@@@.@.@@\n@.@@@@..@.\n@@.@@@@.@@\n\
+                           .@@@@@@@.@\n.@.@.@.@@@\n@.@@@.@@@@\n.@@@@@@@@.\n@.@.@@@.@.\n";
+
+    fn puzzle_rule() -> Rule {
+        Rule::new(Neighbourhood::Moore, 1, 5, Boundary::Empty)
+    }
+
+    fn round_sizes(rounds: &[Round]) -> Vec<usize> {
+        rounds.iter().map(Vec::len).collect()
+    }
+
+    fn grid() -> impl Strategy<Value = Vec<Vec<bool>>> {
+        (1..12usize, 1..12usize).prop_flat_map(|(height, width)| {
+            prop::collection::vec(
+                prop::collection::vec(prop::bool::weighted(0.7), width),
+                height,
+            )
+        })
+    }
+
+    fn rule() -> impl Strategy<Value = Rule> {
+        (
+            prop_oneof![Just(Neighbourhood::VonNeumann), Just(Neighbourhood::Moore)],
+            1..=2usize,
+            0..=10usize,
+            prop_oneof![
+                Just(Boundary::Empty),
+                Just(Boundary::Occupied),
+                Just(Boundary::Wrap)
+            ],
+        )
+            .prop_map(|(neighbourhood, radius, min_empty, boundary)| {
+                Rule::new(neighbourhood, radius, min_empty, boundary)
+            })
+    }
+
+    /// Rounds as sets, since the two solvers remove a round's rolls in
+    /// different orders
+    fn sorted(mut rounds: Vec<Round>) -> Vec<Round> {
+        for round in &mut rounds {
+            round.sort_unstable();
         }
+        rounds
+    }
+
+    proptest! {
+        #[test]
+        fn remove_all_matches_rescan_all(grid in grid(), rule in rule()) {
+            prop_assert_eq!(sorted(remove_all(&grid, &rule)), sorted(rescan_all(&grid, &rule)));
+        }
+
+        #[test]
+        fn sweeping_removes_the_same_rolls(grid in grid(), rule in rule()) {
+            let mut swept: Round = sweep_all(&grid, &rule).concat();
+            let mut removed: Round = remove_all(&grid, &rule).concat();
+            swept.sort_unstable();
+            removed.sort_unstable();
+            prop_assert_eq!(swept, removed);
+        }
+    }
+
+    #[test]
+    fn rejects_ragged_grids() {
+        let rule = Rule::new(Neighbourhood::Moore, 1, 5, Boundary::Wrap);
+        assert!(matches!(
+            solve("@@\n\n@@\n", &rule, &[Semantics::Synchronous], false),
+            Err(GridError::Ragged { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn example_synchronous_removal() {
+        for naive in [false, true] {
+            let Ok(solution) = solve(EXAMPLE, &puzzle_rule(), &[Semantics::Synchronous], naive)
+            else {
+                panic!("the example is a valid grid");
+            };
+            assert_eq!(solution.accessible.len(), 13);
+            let rounds = &solution.removals[0].1;
+            assert_eq!(round_sizes(rounds), [13, 12, 7, 5, 2, 1, 1, 1, 1]);
+            assert_eq!(rounds.concat().len(), 43);
+        }
+    }
+
+    #[test]
+    fn example_sweep_removal() {
+        let Ok(solution) = solve(EXAMPLE, &puzzle_rule(), &[Semantics::Sweep], false) else {
+            panic!("the example is a valid grid");
+        };
+        let rounds = &solution.removals[0].1;
+        // Removals later in a sweep see the earlier ones, so rounds are bigger
+        assert_eq!(round_sizes(rounds), [30, 9, 4]);
+        assert_eq!(rounds.concat().len(), 43);
     }
 
-    println!("Accessible count: {}", accessible_count);
-    println!("Total removed: {}", total_removed);
+    #[test]
+    fn sweep_sees_removals_from_the_same_round() {
+        // The roll at (1, 1) only becomes accessible once both of the rolls
+        // before it in the sweep are gone
+        let rule = Rule::new(Neighbourhood::VonNeumann, 1, 3, Boundary::Empty);
+        let grid = parse_grid(".@.\n@@.\n...\n").ok().unwrap();
+        assert_eq!(sweep_all(&grid, &rule), [vec![(0, 1), (1, 0), (1, 1)]]);
+        assert_eq!(
+            remove_all(&grid, &rule),
+            [vec![(0, 1), (1, 0)], vec![(1, 1)]]
+        );
+    }
+
+    #[test]
+    fn neighbourhood_shapes() {
+        let von_neumann = Rule::new(Neighbourhood::VonNeumann, 2, 0, Boundary::Empty);
+        let moore = Rule::new(Neighbourhood::Moore, 2, 0, Boundary::Empty);
+        assert_eq!(von_neumann.offsets.len(), 12);
+        assert_eq!(moore.offsets.len(), 24);
+    }
+
+    #[test]
+    fn boundaries() {
+        let grid = parse_grid("@..\n...\n..@\n").ok().unwrap();
+        let rule = |boundary| Rule::new(Neighbourhood::Moore, 1, 0, boundary);
+        assert_eq!(rule(Boundary::Empty).empty_neighbours(&grid, 0, 0), 8);
+        assert_eq!(rule(Boundary::Occupied).empty_neighbours(&grid, 0, 0), 3);
+        // Wrapping up and left reaches the opposite corner's roll
+        assert_eq!(rule(Boundary::Wrap).empty_neighbours(&grid, 0, 0), 7);
+    }
+
+    #[test]
+    fn radius_is_capped() {
+        assert!(Day4Args::try_parse_from(["day4", "--radius", "200"]).is_ok());
+        assert!(Day4Args::try_parse_from(["day4", "--radius", "30000"]).is_err());
+    }
+
+    #[test]
+    fn renders_text_with_marks() {
+        let grid = parse_grid("@@.\n.@@\n").ok().unwrap();
+        assert_eq!(render_text(&grid, &[(0, 1), (1, 2)]), "@x.\n.@x\n");
+    }
+
+    #[test]
+    fn writes_frames() {
+        let dir = std::env::temp_dir().join(format!("aoc-day4-frames-{}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let grid = parse_grid("@@.\n.@@\n").ok().unwrap();
+        let rounds = [vec![(0, 0)], vec![(0, 1), (1, 1)]];
+
+        write_frames(&dir, FrameFormat::Text, "sweep", &grid, &rounds).unwrap();
+        let frames: Vec<String> = (0..3)
+            .map(|i| fs::read_to_string(dir.join(format!("sweep-{:04}.txt", i))).unwrap())
+            .collect();
+        assert_eq!(frames, ["x@.\n.@@\n", ".x.\n.x@\n", "...\n..@\n"]);
+
+        write_frames(&dir, FrameFormat::Ppm, "sync", &grid, &rounds).unwrap();
+        let ppm = fs::read(dir.join("sync-0000.ppm")).unwrap();
+        let header = format!("P6\n{} {}\n255\n", 3 * CELL_PIXELS, 2 * CELL_PIXELS);
+        assert!(ppm.starts_with(header.as_bytes()));
+        assert_eq!(
+            ppm.len(),
+            header.len() + 3 * CELL_PIXELS * 2 * CELL_PIXELS * 3
+        );
+        // The first pixel is the marked roll
+        assert_eq!(ppm[header.len()..header.len() + 3], [220, 40, 40]);
+        assert!(dir.join("sync-0002.ppm").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn naive_and_fast_agree_on_generated_inputs() {
+        let options = generate::GridOptions {
+            width: 30,
+            height: 20,
+            density: 0.6,
+        };
+        for seed in 0..5 {
+            let input = generate::day4(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            let semantics = [Semantics::Synchronous];
+            let (Ok(naive), Ok(fast)) = (
+                solve(&input, &puzzle_rule(), &semantics, true),
+                solve(&input, &puzzle_rule(), &semantics, false),
+            ) else {
+                panic!("seed {} does not parse", seed);
+            };
+            assert_eq!(
+                round_sizes(&naive.removals[0].1),
+                round_sizes(&fast.removals[0].1),
+                "seed {}",
+                seed
+            );
+        }
+    }
 }
@@ -1,93 +1,380 @@
-use advent_of_code_2025::Args;
-use clap::Parser;
+use advent_of_code_2025::{Args, range_set::RangeSet};
+use clap::{Parser, ValueEnum};
 
 use nom::{
-    IResult, Parser as _, bytes::complete::tag, character::complete::u64, combinator::map,
-    sequence::separated_pair,
+    IResult, Parser as _,
+    bytes::complete::tag,
+    character::complete::{space0, u64},
+    combinator::eof,
+    sequence::{delimited, separated_pair, terminated},
 };
 
 use std::{
-    fs::File,
-    io::{BufRead, BufReader},
+    fmt::{self, Write as _},
     ops::RangeInclusive,
+    process,
 };
 
-fn parse_fresh_range(input: &str) -> IResult<&str, RangeInclusive<u64>> {
-    map(separated_pair(u64, tag("-"), u64), |(a, b)| a..=b).parse(input)
+#[derive(Parser)]
+#[command(about)]
+struct Day5Args {
+    #[command(flatten)]
+    args: Args,
+
+    /// extra details to print after the answers
+    #[arg(short, long, value_enum, value_delimiter = ',')]
+    report: Vec<Report>,
+
+    /// reject ranges written high-low instead of swapping their ends
+    #[arg(long)]
+    strict: bool,
 }
 
-fn ranges_overlap(r1: &RangeInclusive<u64>, r2: &RangeInclusive<u64>) -> bool {
-    r1.contains(r2.start())
-        || r1.contains(r2.end())
-        || r2.contains(r1.start())
-        || r2.contains(r1.end())
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Report {
+    /// whether each ingredient is fresh, and which input ranges it fell in
+    Ingredients,
+    /// the largest run of spoiled IDs between two fresh ranges
+    Gaps,
+    /// the fresh ranges left after merging overlapping ones
+    Ranges,
 }
 
-/// Ranges should be guaranteed to be overlapping before calling this function
-fn union_ranges(r1: &RangeInclusive<u64>, r2: &RangeInclusive<u64>) -> RangeInclusive<u64> {
-    let start = if r1.start() < r2.start() {
-        r1.start().clone()
-    } else {
-        r2.start().clone()
-    };
-    let end = if r1.end() > r2.end() {
-        r1.end().clone()
-    } else {
-        r2.end().clone()
-    };
+/// A fresh range as written in the input, with the line it came from
+struct InputRange {
+    line: usize,
+    range: RangeInclusive<u64>,
+}
 
-    start..=end
+fn ingredient_report(ingredients: &[u64], ranges: &RangeSet<u64>, inputs: &[InputRange]) -> String {
+    let mut report = String::new();
+    let mut spoiled_count = 0;
+    for &ingredient in ingredients {
+        if ranges.contains(ingredient) {
+            let matches: Vec<String> = inputs
+                .iter()
+                .filter(|input| input.range.contains(&ingredient))
+                .map(|input| {
+                    format!(
+                        "{}-{} (line {})",
+                        input.range.start(),
+                        input.range.end(),
+                        input.line
+                    )
+                })
+                .collect();
+            writeln!(report, "{}: fresh, in {}", ingredient, matches.join(", ")).unwrap();
+        } else {
+            writeln!(report, "{}: spoiled", ingredient).unwrap();
+            spoiled_count += 1;
+        }
+    }
+    writeln!(report, "Spoiled ingredient count: {}", spoiled_count).unwrap();
+    report
 }
 
-fn main() {
-    let args = Args::parse();
+fn gap_report(ranges: &RangeSet<u64>) -> String {
+    let largest_gap = ranges
+        .ranges()
+        .zip(ranges.ranges().skip(1))
+        .map(|(prev, next)| (prev.end() + 1)..=(next.start() - 1))
+        .max_by_key(|gap| gap.end() - gap.start());
 
-    let input_file = File::open(args.input).expect("Could not find file");
-    let reader = BufReader::new(input_file);
+    match largest_gap {
+        Some(gap) => format!(
+            "Largest gap: {}-{} ({} spoiled IDs)\n",
+            gap.start(),
+            gap.end(),
+            gap.end() - gap.start() + 1
+        ),
+        None => "Largest gap: none, there are fewer than two fresh ranges\n".to_string(),
+    }
+}
 
-    let mut reading_fresh_ranges = true;
-    let mut ranges = Vec::new();
-    let mut ingredients = Vec::new();
-    for line in reader.lines() {
-        let line = line.expect("Could not read line");
+fn range_report(ranges: &RangeSet<u64>) -> String {
+    let mut report = format!("Merged range count: {}\n", ranges.range_count());
+    for range in ranges.ranges() {
+        writeln!(report, "{}-{}", range.start(), range.end()).unwrap();
+    }
+    report
+}
 
-        if reading_fresh_ranges {
-            if line == "" {
-                reading_fresh_ranges = false;
-                continue;
+/// Parses `lo-hi`, returning the ends in the order they were written
+fn parse_fresh_range(input: &str) -> IResult<&str, (u64, u64)> {
+    terminated(
+        separated_pair(u64, delimited(space0, tag("-"), space0), u64),
+        eof,
+    )
+    .parse(input)
+}
+
+struct Inventory {
+    ranges: Vec<InputRange>,
+    ingredients: Vec<u64>,
+}
+
+enum InventoryError {
+    InvalidRange { line: usize },
+    ReversedRange { line: usize, start: u64, end: u64 },
+    InvalidIngredient { line: usize },
+    MissingRanges,
+    MissingIngredients,
+}
+
+impl fmt::Display for InventoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InventoryError::InvalidRange { line } => {
+                write!(f, "Could not parse fresh range on line {}", line)
             }
-            let (_, mut new_range) = parse_fresh_range(&line).expect("Could not parse fresh range");
-            let mut i = 0;
-            while i < ranges.len() {
-                if ranges_overlap(&ranges[i], &new_range) {
-                    new_range = union_ranges(&ranges[i], &new_range);
-                    ranges.swap_remove(i);
-                } else {
-                    i += 1;
-                }
+            InventoryError::ReversedRange { line, start, end } => write!(
+                f,
+                "Fresh range {}-{} on line {} ends before it starts",
+                start, end, line
+            ),
+            InventoryError::InvalidIngredient { line } => {
+                write!(f, "Could not parse ingredient on line {}", line)
             }
-            ranges.push(new_range.clone());
-        } else {
-            let ingredient = line.parse::<u64>().expect("Could not parse ingredient");
-            ingredients.push(ingredient);
+            InventoryError::MissingRanges => write!(f, "Input has no fresh ranges"),
+            InventoryError::MissingIngredients => write!(
+                f,
+                "Input has no ingredients after the fresh ranges; the sections must be separated by a blank line"
+            ),
         }
     }
+}
 
-    let mut fresh_ingredient_count = 0;
-    for ingredient in ingredients {
-        for range in &ranges {
-            if range.contains(&ingredient) {
-                fresh_ingredient_count += 1;
-                break;
+/// Reads the fresh ranges, then the ingredients after the first blank line.
+///
+/// Surrounding whitespace and stray `\r`s are ignored, as are blank lines
+/// before the ranges and anywhere after them. Ranges written high-low have
+/// their ends swapped unless `strict` is set.
+fn parse_inventory<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    strict: bool,
+) -> Result<Inventory, InventoryError> {
+    let mut inventory = Inventory {
+        ranges: Vec::new(),
+        ingredients: Vec::new(),
+    };
+    let mut reading_fresh_ranges = true;
+    for (line_num, line) in lines.enumerate() {
+        let line = line.trim();
+        let line_num = line_num + 1;
+
+        if line.is_empty() {
+            if !inventory.ranges.is_empty() {
+                reading_fresh_ranges = false;
             }
+        } else if reading_fresh_ranges {
+            let (_, (start, end)) = parse_fresh_range(line)
+                .map_err(|_| InventoryError::InvalidRange { line: line_num })?;
+            if start > end && strict {
+                return Err(InventoryError::ReversedRange {
+                    line: line_num,
+                    start,
+                    end,
+                });
+            }
+            inventory.ranges.push(InputRange {
+                line: line_num,
+                range: start.min(end)..=start.max(end),
+            });
+        } else {
+            let ingredient = line
+                .parse::<u64>()
+                .map_err(|_| InventoryError::InvalidIngredient { line: line_num })?;
+            inventory.ingredients.push(ingredient);
         }
     }
 
-    let mut fresh_count = 0;
-    for range in ranges {
-        fresh_count += range.count();
+    if inventory.ranges.is_empty() {
+        Err(InventoryError::MissingRanges)
+    } else if inventory.ingredients.is_empty() {
+        Err(InventoryError::MissingIngredients)
+    } else {
+        Ok(inventory)
     }
+}
+
+struct Solution {
+    inputs: Vec<InputRange>,
+    ingredients: Vec<u64>,
+    /// Every fresh ingredient ID, merged from all of the ranges
+    ranges: RangeSet<u64>,
+    fresh_ingredient_count: usize,
+}
+
+fn solve(input: &str, strict: bool) -> Result<Solution, InventoryError> {
+    let Inventory {
+        ranges: inputs,
+        ingredients,
+    } = parse_inventory(input.lines(), strict)?;
+    let ranges: RangeSet<u64> = inputs.iter().map(|input| input.range.clone()).collect();
+
+    let fresh_ingredient_count = ingredients
+        .iter()
+        .filter(|&&ingredient| ranges.contains(ingredient))
+        .count();
+
+    Ok(Solution {
+        inputs,
+        ingredients,
+        ranges,
+        fresh_ingredient_count,
+    })
+}
+
+fn main() {
+    let Day5Args {
+        args,
+        report,
+        strict,
+    } = Day5Args::parse();
+
+    let input = args.load_input(5).expect("Could not load input");
+
+    let Solution {
+        inputs,
+        ingredients,
+        ranges,
+        fresh_ingredient_count,
+    } = solve(&input, strict).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
 
     println!("Fresh ingredient count: {}", fresh_ingredient_count);
-    println!("Fresh count: {}", fresh_count);
+    println!("Fresh count: {}", ranges.len());
+
+    if report.contains(&Report::Ingredients) {
+        print!("{}", ingredient_report(&ingredients, &ranges, &inputs));
+    }
+    if report.contains(&Report::Gaps) {
+        print!("{}", gap_report(&ranges));
+    }
+    if report.contains(&Report::Ranges) {
+        print!("{}", range_report(&ranges));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const EXAMPLE: &str = "3-5\n10-14\n16-20\n12-18\n\n1\n5\n8\n11\n17\n32\n";
+
+    fn counts(input: &str, strict: bool) -> Option<(usize, u128)> {
+        let solution = solve(input, strict).ok()?;
+        Some((solution.fresh_ingredient_count, solution.ranges.len()))
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(counts(EXAMPLE, false), Some((3, 14)));
+    }
+
+    #[test]
+    fn blank_separator_may_have_trailing_spaces() {
+        let input = EXAMPLE.replace("\n\n", "\n  \t\n");
+        assert_eq!(counts(&input, false), Some((3, 14)));
+    }
+
+    #[test]
+    fn crlf_line_endings_are_accepted() {
+        let input = EXAMPLE.replace('\n', "\r\n");
+        assert_eq!(counts(&input, false), Some((3, 14)));
+    }
+
+    #[test]
+    fn reversed_ranges_are_swapped() {
+        let input = EXAMPLE.replace("10-14", "14-10");
+        assert_eq!(counts(&input, false), Some((3, 14)));
+    }
+
+    #[test]
+    fn reversed_ranges_are_rejected_when_strict() {
+        let input = EXAMPLE.replace("10-14", "14-10");
+        assert!(matches!(
+            solve(&input, true),
+            Err(InventoryError::ReversedRange {
+                line: 2,
+                start: 14,
+                end: 10
+            })
+        ));
+    }
+
+    #[test]
+    fn missing_ranges_are_reported() {
+        assert!(matches!(
+            solve("\n\n", false),
+            Err(InventoryError::MissingRanges)
+        ));
+    }
+
+    #[test]
+    fn missing_ingredients_are_reported() {
+        assert!(matches!(
+            solve("3-5\n10-14\n", false),
+            Err(InventoryError::MissingIngredients)
+        ));
+        // Without a blank line the ingredients read as bad ranges
+        assert!(matches!(
+            solve("3-5\n1\n", false),
+            Err(InventoryError::InvalidRange { line: 2 })
+        ));
+    }
+
+    #[test]
+    fn example_reports() {
+        let Ok(solution) = solve(EXAMPLE, false) else {
+            panic!("the example is a valid inventory");
+        };
+        assert_eq!(
+            ingredient_report(&solution.ingredients, &solution.ranges, &solution.inputs),
+            "1: spoiled\n5: fresh, in 3-5 (line 1)\n8: spoiled\n11: fresh, in 10-14 (line 2)\n\
+             17: fresh, in 16-20 (line 3), 12-18 (line 4)\n32: spoiled\nSpoiled ingredient count: 3\n"
+        );
+        assert_eq!(
+            gap_report(&solution.ranges),
+            "Largest gap: 6-9 (4 spoiled IDs)\n"
+        );
+        assert_eq!(
+            range_report(&solution.ranges),
+            "Merged range count: 2\n3-5\n10-20\n"
+        );
+    }
+
+    #[test]
+    fn gap_report_picks_the_largest_gap() {
+        let ranges: RangeSet<u64> = [0..=1, 3..=4, 10..=10, 12..=u64::MAX].into_iter().collect();
+        assert_eq!(gap_report(&ranges), "Largest gap: 5-9 (5 spoiled IDs)\n");
+    }
+
+    #[test]
+    fn gap_report_needs_two_ranges() {
+        let ranges: RangeSet<u64> = [3..=5, 4..=9].into_iter().collect();
+        assert_eq!(
+            gap_report(&ranges),
+            "Largest gap: none, there are fewer than two fresh ranges\n"
+        );
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        let options = generate::Day5Options {
+            ranges: 20,
+            ingredients: 50,
+            max_id: 10_000,
+            max_width: 1000,
+        };
+        for seed in 0..5 {
+            let input = generate::day5(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            assert!(solve(&input, true).is_ok(), "seed {}", seed);
+        }
+    }
 }
@@ -1,135 +1,668 @@
 use advent_of_code_2025::Args;
 use clap::Parser;
 
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
-
-use nom::{
-    IResult, Parser as _,
-    branch::alt,
-    bytes::tag,
-    character::complete::{space1, u64},
-    combinator::map,
-    multi::separated_list1,
-};
+use std::{fmt, ops::Range, process};
 
+use nom::{IResult, Parser as _, branch::alt, bytes::tag, combinator::value};
+
+#[derive(Parser)]
+#[command(about)]
+struct Day6Args {
+    #[command(flatten)]
+    args: Args,
+
+    /// print the operands of every problem under both reading orders
+    #[arg(short, long)]
+    verbose: bool,
+
+    /// how many columns a tab advances to the next tab stop
+    #[arg(long, default_value_t = 8, value_parser = clap::value_parser!(u8).range(1..))]
+    tab_width: u8,
+}
+
+#[derive(Clone, Copy)]
 enum MathOp {
     Multiply,
     Add,
+    Subtract,
+    /// Integer division, discarding any remainder
+    Divide,
+    Max,
+    Min,
+    /// Raises each operand to the power of everything after it, so `2 ^ 3 ^ 2`
+    /// is `2 ^ 9`
+    Power,
+}
+
+#[derive(Debug)]
+enum EvalError {
+    Overflow,
+    /// A subtraction went below zero
+    Underflow,
+    DivideByZero,
+    NoOperands,
 }
 
-fn parse_num_row(input: &str) -> IResult<&str, Vec<u64>> {
-    separated_list1(space1, u64).parse(input)
+impl MathOp {
+    fn symbol(self) -> &'static str {
+        match self {
+            MathOp::Multiply => "*",
+            MathOp::Add => "+",
+            MathOp::Subtract => "-",
+            MathOp::Divide => "/",
+            MathOp::Max => "max",
+            MathOp::Min => "min",
+            MathOp::Power => "^",
+        }
+    }
+
+    /// Combines the operands left to right (right to left for powers),
+    /// failing instead of wrapping if a step overflows or goes below zero
+    fn evaluate(self, operands: &[u64]) -> Result<u64, EvalError> {
+        let (&first, rest) = operands.split_first().ok_or(EvalError::NoOperands)?;
+        match self {
+            MathOp::Multiply => rest.iter().try_fold(first, |acc, &n| acc.checked_mul(n)),
+            MathOp::Add => rest.iter().try_fold(first, |acc, &n| acc.checked_add(n)),
+            MathOp::Subtract => {
+                return rest
+                    .iter()
+                    .try_fold(first, |acc, &n| acc.checked_sub(n))
+                    .ok_or(EvalError::Underflow);
+            }
+            MathOp::Divide => {
+                if rest.contains(&0) {
+                    return Err(EvalError::DivideByZero);
+                }
+                Some(rest.iter().fold(first, |acc, &n| acc / n))
+            }
+            MathOp::Max => Some(rest.iter().fold(first, |acc, &n| acc.max(n))),
+            MathOp::Min => Some(rest.iter().fold(first, |acc, &n| acc.min(n))),
+            MathOp::Power => operands.iter().rev().skip(1).try_fold(
+                *operands.last().expect("operands are not empty"),
+                |exp, &base| match (base, exp) {
+                    (_, 0) => Some(1),
+                    // These stay put however big the exponent is
+                    (0 | 1, _) => Some(base),
+                    _ => base.checked_pow(u32::try_from(exp).ok()?),
+                },
+            ),
+        }
+        .ok_or(EvalError::Overflow)
+    }
 }
 
 fn parse_math_op(input: &str) -> IResult<&str, MathOp> {
     alt((
-        map(tag("+"), |_| MathOp::Add),
-        map(tag("*"), |_| MathOp::Multiply),
+        value(MathOp::Add, tag("+")),
+        value(MathOp::Multiply, tag("*")),
+        value(MathOp::Subtract, tag("-")),
+        value(MathOp::Divide, tag("/")),
+        value(MathOp::Max, tag("max")),
+        value(MathOp::Min, tag("min")),
+        value(MathOp::Power, tag("^")),
     ))
     .parse(input)
 }
 
-fn parse_op_row(input: &str) -> IResult<&str, Vec<MathOp>> {
-    separated_list1(space1, parse_math_op).parse(input)
+/// An evaluation failure in the problem starting at the given column
+struct ProblemError {
+    column: usize,
+    error: EvalError,
 }
 
-fn parse_col_num(char_grid: &Vec<Vec<char>>, col: usize) -> u64 {
-    let mut col_num_str = String::new();
-    for i in 0..char_grid.len() - 1 {
-        col_num_str.push(char_grid[i][col]);
+impl fmt::Display for ProblemError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self.error {
+            EvalError::Overflow => "overflowed",
+            EvalError::Underflow => "went below zero",
+            EvalError::DivideByZero => "divided by zero",
+            EvalError::NoOperands => "has no operands",
+        };
+        write!(f, "Problem at column {} {}", self.column + 1, reason)
     }
-    col_num_str
-        .trim()
-        .parse()
-        .expect("Unable to parse into u64")
 }
 
-fn main() {
-    let args = Args::parse();
-
-    let input_file = File::open(args.input).expect("Could not find file");
-    let reader = BufReader::new(input_file);
-
-    let mut num_grid = Vec::new();
-    let mut char_grid: Vec<Vec<char>> = Vec::new();
-    let mut ops = Vec::new();
-    let mut empty_cols = Vec::new();
-    let mut first_row = true;
-    for line in reader.lines() {
-        let raw_line = line.expect("Could not read line");
-        let line = raw_line.trim();
-        if let Ok((_, num_row)) = parse_num_row(&line) {
-            num_grid.push(num_row);
-        } else {
-            let (_, op_row) = parse_op_row(&line).expect("Could not parse op row");
-            ops.extend(op_row);
+/// Why the worksheet's columns could not be split into problems. Lines and
+/// columns are 1-based, with columns counted after tabs are expanded.
+enum LayoutError {
+    Empty,
+    MissingOperator {
+        line: usize,
+        columns: Range<usize>,
+    },
+    StrayOperator {
+        line: usize,
+        column: usize,
+    },
+    /// A second operator in one problem, left when a wide operator fills the
+    /// blank column that should separate two problems
+    CrowdedOperator {
+        line: usize,
+        column: usize,
+    },
+    InvalidOperator {
+        line: usize,
+        column: usize,
+    },
+    InvalidNumber {
+        line: usize,
+        columns: Range<usize>,
+    },
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::Empty => write!(f, "Worksheet needs rows of numbers and an operator row"),
+            LayoutError::MissingOperator { line, columns } => write!(
+                f,
+                "Operator row on line {} has no operator under columns {}-{}",
+                line, columns.start, columns.end
+            ),
+            LayoutError::StrayOperator { line, column } => write!(
+                f,
+                "Operator on line {}, column {} is not under any problem",
+                line, column
+            ),
+            LayoutError::CrowdedOperator { line, column } => write!(
+                f,
+                "Operator on line {}, column {} needs a blank column between it and the operator before it",
+                line, column
+            ),
+            LayoutError::InvalidOperator { line, column } => write!(
+                f,
+                "Could not parse operator on line {}, column {}",
+                line, column
+            ),
+            LayoutError::InvalidNumber { line, columns } => write!(
+                f,
+                "Could not parse number on line {}, columns {}-{}",
+                line, columns.start, columns.end
+            ),
         }
+    }
+}
 
-        if first_row {
-            empty_cols = raw_line
-                .char_indices()
-                .filter(|(_, c)| *c == ' ')
-                .map(|(i, _)| i)
-                .collect();
+/// Replaces tabs with spaces up to the next multiple of `tab_width`
+fn expand_tabs(line: &str, tab_width: usize) -> Vec<char> {
+    let mut expanded = Vec::with_capacity(line.len());
+    for c in line.chars() {
+        if c == '\t' {
+            let stop = (expanded.len() / tab_width + 1) * tab_width;
+            expanded.resize(stop, ' ');
         } else {
-            for (i, c) in raw_line.char_indices() {
-                if c != ' ' && empty_cols.contains(&i) {
-                    empty_cols.remove(empty_cols.binary_search(&i).unwrap());
+            expanded.push(c);
+        }
+    }
+    expanded
+}
+
+fn read_number(digits: impl Iterator<Item = char>) -> Option<u64> {
+    digits.collect::<String>().trim().parse().ok()
+}
+
+/// A single problem on the worksheet: a block of columns with an operator
+/// underneath
+struct Problem {
+    columns: Range<usize>,
+    op: MathOp,
+}
+
+struct Worksheet {
+    /// The rows of numbers above the operator row, padded to the same width
+    num_rows: Vec<Vec<char>>,
+    problems: Vec<Problem>,
+}
+
+impl Worksheet {
+    /// Splits the grid into problems at the columns that are blank in every
+    /// line. The last non-blank line holds the operators.
+    ///
+    /// Tabs are expanded and short lines are padded with spaces first, so
+    /// trailing whitespace trimmed by an editor does not shift any columns.
+    /// Every operand of every problem is checked to be a number under both
+    /// reading orders.
+    fn parse(lines: &[&str], tab_width: usize) -> Result<Worksheet, LayoutError> {
+        let mut rows: Vec<Vec<char>> = lines
+            .iter()
+            .map(|line| expand_tabs(line.trim_end_matches('\r'), tab_width))
+            .collect();
+        while rows
+            .last()
+            .is_some_and(|row| row.iter().all(|c| c.is_whitespace()))
+        {
+            rows.pop();
+        }
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, ' ');
+        }
+
+        let (op_row, num_rows) = rows.split_last().ok_or(LayoutError::Empty)?;
+        if num_rows.is_empty() {
+            return Err(LayoutError::Empty);
+        }
+        let op_line = rows.len();
+        let is_separator = |col: usize| rows.iter().all(|row| row[col] == ' ');
+
+        let mut problems = Vec::new();
+        let mut start = 0;
+        for col in 0..=width {
+            if col == width || is_separator(col) {
+                if start < col {
+                    problems.push(Worksheet::parse_problem(
+                        op_row,
+                        num_rows,
+                        op_line,
+                        start..col,
+                    )?);
                 }
+                start = col + 1;
             }
         }
-        char_grid.push(raw_line.chars().collect());
 
-        if first_row {
-            first_row = false;
-        }
+        Ok(Worksheet {
+            num_rows: num_rows.to_vec(),
+            problems,
+        })
     }
 
-    let mut calc_sum = 0;
-    for col in 0..num_grid[0].len() {
-        let cur_op = &ops[col];
-        let mut res = match cur_op {
-            MathOp::Multiply => 1,
-            MathOp::Add => 0,
+    fn parse_problem(
+        op_row: &[char],
+        num_rows: &[Vec<char>],
+        op_line: usize,
+        columns: Range<usize>,
+    ) -> Result<Problem, LayoutError> {
+        let human_columns = columns.start + 1..columns.end;
+        let op_start = match op_row[columns.clone()].iter().position(|&c| c != ' ') {
+            Some(offset) => columns.start + offset,
+            None => {
+                return Err(LayoutError::MissingOperator {
+                    line: op_line,
+                    columns: human_columns,
+                });
+            }
         };
-        for row in 0..num_grid.len() {
-            match cur_op {
-                MathOp::Multiply => {
-                    res *= num_grid[row][col];
-                }
-                MathOp::Add => {
-                    res += num_grid[row][col];
-                }
+        if num_rows
+            .iter()
+            .all(|row| row[columns.clone()].iter().all(|&c| c == ' '))
+        {
+            return Err(LayoutError::StrayOperator {
+                line: op_line,
+                column: op_start + 1,
+            });
+        }
+
+        let op_end = op_row[op_start..columns.end]
+            .iter()
+            .position(|&c| c == ' ')
+            .map_or(columns.end, |offset| op_start + offset);
+        if let Some(offset) = op_row[op_end..columns.end].iter().position(|&c| c != ' ') {
+            return Err(LayoutError::CrowdedOperator {
+                line: op_line,
+                column: op_end + offset + 1,
+            });
+        }
+
+        let op_str: String = op_row[op_start..op_end].iter().collect();
+        let op = match parse_math_op(&op_str) {
+            Ok(("", op)) => op,
+            _ => {
+                return Err(LayoutError::InvalidOperator {
+                    line: op_line,
+                    column: op_start + 1,
+                });
+            }
+        };
+
+        for (i, row) in num_rows.iter().enumerate() {
+            if read_number(row[columns.clone()].iter().copied()).is_none() {
+                return Err(LayoutError::InvalidNumber {
+                    line: i + 1,
+                    columns: human_columns,
+                });
+            }
+        }
+        for col in columns.clone() {
+            let digits: Vec<char> = num_rows.iter().map(|row| row[col]).collect();
+            if read_number(digits.iter().copied()).is_none() && digits.iter().any(|&c| c != ' ') {
+                // Rows already parsed, so the column must have a gap between its digits
+                let first_digit = digits.iter().position(|&c| c != ' ').unwrap_or(0);
+                let gap = digits[first_digit..]
+                    .iter()
+                    .position(|&c| c == ' ')
+                    .map_or(0, |offset| first_digit + offset);
+                return Err(LayoutError::InvalidNumber {
+                    line: gap + 1,
+                    columns: col + 1..col + 1,
+                });
             }
         }
-        calc_sum += res;
+
+        Ok(Problem { columns, op })
+    }
+
+    /// Reads each row of the problem as a number, the way a human would
+    fn row_operands(&self, problem: &Problem) -> Vec<u64> {
+        self.num_rows
+            .iter()
+            .map(|row| {
+                read_number(row[problem.columns.clone()].iter().copied())
+                    .expect("Operands are checked when parsing")
+            })
+            .collect()
     }
 
-    let mut ceph_sum: u64 = 0;
-    let mut operands = Vec::new();
-    let mut op_num = 0;
-    for col in 0..char_grid[0].len() {
-        if empty_cols.contains(&col) {
-            ceph_sum += match ops[op_num] {
-                MathOp::Multiply => operands.iter().product::<u64>(),
-                MathOp::Add => operands.iter().sum(),
+    /// Reads each column of the problem top to bottom as a number, taking the
+    /// columns right to left the way a cephalopod would. Columns that only hold
+    /// part of a wide operator are skipped.
+    fn column_operands(&self, problem: &Problem) -> Vec<u64> {
+        problem
+            .columns
+            .clone()
+            .rev()
+            .filter(|&col| self.num_rows.iter().any(|row| row[col] != ' '))
+            .map(|col| {
+                read_number(self.num_rows.iter().map(|row| row[col]))
+                    .expect("Operands are checked when parsing")
+            })
+            .collect()
+    }
+
+    fn row_sum(&self) -> Result<u64, ProblemError> {
+        self.sum_with(|problem| self.row_operands(problem))
+    }
+
+    fn column_sum(&self) -> Result<u64, ProblemError> {
+        self.sum_with(|problem| self.column_operands(problem))
+    }
+
+    fn sum_with(&self, operands: impl Fn(&Problem) -> Vec<u64>) -> Result<u64, ProblemError> {
+        self.problems.iter().try_fold(0u64, |sum, problem| {
+            let fail = |error| ProblemError {
+                column: problem.columns.start,
+                error,
             };
-            op_num += 1;
-            operands.clear();
-        } else {
-            operands.push(parse_col_num(&char_grid, col));
-        }
+            let res = problem.op.evaluate(&operands(problem)).map_err(fail)?;
+            sum.checked_add(res)
+                .ok_or_else(|| fail(EvalError::Overflow))
+        })
     }
+}
 
-    // Finish the last operation
-    ceph_sum += match ops[op_num] {
-        MathOp::Multiply => operands.iter().product::<u64>(),
-        MathOp::Add => operands.iter().sum(),
+fn describe(operands: &[u64], op: MathOp) -> String {
+    let terms: Vec<String> = operands.iter().map(|n| n.to_string()).collect();
+    let res = match op.evaluate(operands) {
+        Ok(res) => res.to_string(),
+        Err(e) => format!("{:?}", e),
     };
+    format!("{} = {}", terms.join(&format!(" {} ", op.symbol())), res)
+}
+
+enum WorksheetError {
+    Layout(LayoutError),
+    Problem(ProblemError),
+}
+
+impl fmt::Display for WorksheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorksheetError::Layout(e) => e.fmt(f),
+            WorksheetError::Problem(e) => e.fmt(f),
+        }
+    }
+}
+
+struct Solution {
+    worksheet: Worksheet,
+    row_sum: u64,
+    column_sum: u64,
+}
+
+fn solve(input: &str, tab_width: usize) -> Result<Solution, WorksheetError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let worksheet = Worksheet::parse(&lines, tab_width).map_err(WorksheetError::Layout)?;
+    let row_sum = worksheet.row_sum().map_err(WorksheetError::Problem)?;
+    let column_sum = worksheet.column_sum().map_err(WorksheetError::Problem)?;
+
+    Ok(Solution {
+        worksheet,
+        row_sum,
+        column_sum,
+    })
+}
+
+fn main() {
+    let Day6Args {
+        args,
+        verbose,
+        tab_width,
+    } = Day6Args::parse();
+
+    let input = args.load_input(6).expect("Could not load input");
+    let Solution {
+        worksheet,
+        row_sum,
+        column_sum,
+    } = solve(&input, tab_width as usize).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+
+    if verbose {
+        for (i, problem) in worksheet.problems.iter().enumerate() {
+            println!(
+                "Problem {} (columns {}-{}):",
+                i + 1,
+                problem.columns.start + 1,
+                problem.columns.end
+            );
+            println!(
+                "  rows:    {}",
+                describe(&worksheet.row_operands(problem), problem.op)
+            );
+            println!(
+                "  columns: {}",
+                describe(&worksheet.column_operands(problem), problem.op)
+            );
+        }
+    }
+
+    println!("Calc sum: {}", row_sum);
+    println!("Ceph sum: {}", column_sum);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    const EXAMPLE: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  \n";
+
+    fn example() -> Worksheet {
+        let lines: Vec<&str> = EXAMPLE.lines().collect();
+        let Ok(worksheet) = Worksheet::parse(&lines, 8) else {
+            panic!("the example is a valid worksheet");
+        };
+        worksheet
+    }
+
+    #[test]
+    fn example_row_operands() {
+        let worksheet = example();
+        let operands: Vec<Vec<u64>> = worksheet
+            .problems
+            .iter()
+            .map(|problem| worksheet.row_operands(problem))
+            .collect();
+        assert_eq!(
+            operands,
+            [
+                vec![123, 45, 6],
+                vec![328, 64, 98],
+                vec![51, 387, 215],
+                vec![64, 23, 314]
+            ]
+        );
+        assert_eq!(worksheet.row_sum().ok(), Some(4277556));
+    }
+
+    #[test]
+    fn example_column_operands() {
+        let worksheet = example();
+        let operands: Vec<Vec<u64>> = worksheet
+            .problems
+            .iter()
+            .map(|problem| worksheet.column_operands(problem))
+            .collect();
+        assert_eq!(
+            operands,
+            [
+                vec![356, 24, 1],
+                vec![8, 248, 369],
+                vec![175, 581, 32],
+                vec![4, 431, 623]
+            ]
+        );
+        assert_eq!(worksheet.column_sum().ok(), Some(3263827));
+    }
 
-    println!("Calc sum: {}", calc_sum);
-    println!("Ceph sum: {}", ceph_sum);
+    #[test]
+    fn operators() {
+        assert_eq!(MathOp::Subtract.evaluate(&[20, 5, 3]).ok(), Some(12));
+        assert_eq!(MathOp::Divide.evaluate(&[100, 3, 2]).ok(), Some(16));
+        assert_eq!(MathOp::Max.evaluate(&[4, 9, 2]).ok(), Some(9));
+        assert_eq!(MathOp::Min.evaluate(&[4, 9, 2]).ok(), Some(2));
+        assert_eq!(MathOp::Power.evaluate(&[2, 3, 2]).ok(), Some(512));
+        assert_eq!(MathOp::Power.evaluate(&[7]).ok(), Some(7));
+    }
+
+    #[test]
+    fn operator_errors() {
+        assert!(matches!(
+            MathOp::Multiply.evaluate(&[u64::MAX, 2]),
+            Err(EvalError::Overflow)
+        ));
+        assert!(matches!(
+            MathOp::Power.evaluate(&[2, 64]),
+            Err(EvalError::Overflow)
+        ));
+        assert!(matches!(
+            MathOp::Subtract.evaluate(&[3, 5]),
+            Err(EvalError::Underflow)
+        ));
+        assert!(matches!(
+            MathOp::Divide.evaluate(&[3, 0]),
+            Err(EvalError::DivideByZero)
+        ));
+        assert!(matches!(
+            MathOp::Add.evaluate(&[]),
+            Err(EvalError::NoOperands)
+        ));
+    }
+
+    #[test]
+    fn evaluation_errors_name_the_problem_column() {
+        let Err(WorksheetError::Problem(e)) = solve("1 3 4\n2 0 5\n+ / -\n", 8) else {
+            panic!("the middle problem divides by zero");
+        };
+        assert_eq!(e.to_string(), "Problem at column 3 divided by zero");
+
+        let Err(WorksheetError::Problem(e)) = solve("1 3 4\n2 1 5\n+ / -\n", 8) else {
+            panic!("the last problem goes below zero");
+        };
+        assert_eq!(e.to_string(), "Problem at column 5 went below zero");
+    }
+
+    #[test]
+    fn ragged_lines_are_padded() {
+        let trimmed: String = EXAMPLE
+            .lines()
+            .map(|line| line.trim_end().to_owned() + "\n")
+            .collect();
+        let Ok(solution) = solve(&trimmed, 8) else {
+            panic!("trimming trailing spaces keeps the worksheet valid");
+        };
+        assert_eq!((solution.row_sum, solution.column_sum), (4277556, 3263827));
+    }
+
+    #[test]
+    fn tabs_expand_to_the_next_stop() {
+        assert_eq!(
+            expand_tabs("ab\tc\t\td", 4),
+            "ab  c       d".chars().collect::<Vec<_>>()
+        );
+
+        for tab_width in [4, 8] {
+            let Ok(solution) = solve("12\t3\n4\t5\n+\t*\n", tab_width) else {
+                panic!("tabs line the problems up");
+            };
+            assert_eq!((solution.row_sum, solution.column_sum), (31, 51));
+        }
+    }
+
+    #[test]
+    fn missing_operators_are_reported() {
+        assert!(matches!(
+            solve("1 2\n3 4\n+\n", 8),
+            Err(WorksheetError::Layout(LayoutError::MissingOperator { line: 3, columns })) if columns == (3..3)
+        ));
+    }
+
+    #[test]
+    fn stray_operators_are_reported() {
+        assert!(matches!(
+            solve("1\n3\n+ *\n", 8),
+            Err(WorksheetError::Layout(LayoutError::StrayOperator {
+                line: 3,
+                column: 3
+            }))
+        ));
+    }
+
+    #[test]
+    fn huge_exponents_of_zero_and_one() {
+        assert_eq!(MathOp::Power.evaluate(&[1, 5_000_000_000]).ok(), Some(1));
+        assert_eq!(MathOp::Power.evaluate(&[0, 5_000_000_000]).ok(), Some(0));
+        assert_eq!(MathOp::Power.evaluate(&[0, 0]).ok(), Some(1));
+        assert_eq!(MathOp::Power.evaluate(&[5_000_000_000, 0]).ok(), Some(1));
+        // 1 ^ (2 ^ 40) is 1 even though the exponent does not fit in a u32
+        assert_eq!(MathOp::Power.evaluate(&[1, 2, 40]).ok(), Some(1));
+        assert!(matches!(
+            MathOp::Power.evaluate(&[2, 5_000_000_000]),
+            Err(EvalError::Overflow)
+        ));
+    }
+
+    #[test]
+    fn wide_operators_must_not_reach_the_next_problem() {
+        assert!(matches!(
+            solve("12 3\n 4 5\nmax ^\n", 8),
+            Err(WorksheetError::Layout(LayoutError::CrowdedOperator {
+                line: 3,
+                column: 5
+            }))
+        ));
+        let Ok(solution) = solve("12  3\n 4  5\nmax ^\n", 8) else {
+            panic!("a blank column separates the problems");
+        };
+        assert_eq!((solution.row_sum, solution.column_sum), (12 + 243, 24 + 35));
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        for rows in 1..=4 {
+            let options = generate::Day6Options {
+                problems: 50,
+                rows,
+                max_digits: 4,
+            };
+            for seed in 0..5 {
+                let input = generate::day6(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+                assert!(solve(&input, 8).is_ok(), "{} rows, seed {}", rows, seed);
+            }
+        }
+    }
 }
@@ -1,12 +1,15 @@
-use std::{
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{fmt, process};
 
 use advent_of_code_2025::Args;
 use clap::Parser;
-use nom::{IResult, Parser as _, branch::alt, bytes::complete::tag, combinator::map, multi::many1};
+use nom::{
+    IResult, Parser as _,
+    branch::alt,
+    bytes::complete::tag,
+    combinator::{eof, map},
+    multi::many1,
+    sequence::terminated,
+};
 
 #[derive(Clone, PartialEq, Debug)]
 enum SpaceState {
@@ -27,11 +30,6 @@ struct SimulationResult {
     end_locs: Vec<usize>,
 }
 
-#[derive(Default)]
-struct QuantumSimResult {
-    ends: u64,
-}
-
 impl Grid {
     fn add_row(&mut self, new_row: Vec<SpaceState>) {
         self.grid.push(new_row);
@@ -70,65 +68,48 @@ impl Grid {
             }
         }
 
-        res.end_locs = working_grid[working_grid.len() - 1]
-            .iter()
-            .enumerate()
-            .filter(|(_, s)| **s == SpaceState::Beam)
-            .map(|(i, _)| i)
-            .collect();
+        if let Some(last_row) = working_grid.last() {
+            res.end_locs = last_row
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| **s == SpaceState::Beam)
+                .map(|(i, _)| i)
+                .collect();
+        }
 
         res
     }
 
-    fn simulate_quantum(&self) -> u64 {
-        let mut memo = HashMap::new();
-        let start_pos = self.grid[0]
-            .iter()
-            .enumerate()
-            .find_map(|(i, s)| match s {
-                SpaceState::Start => Some(i),
-                _ => None,
-            })
-            .expect("Start was not on the first row");
-        self.help_sim_quantum((0, start_pos), &mut memo)
-    }
+    /// Counts the timelines a single particle ends up in, failing if there are
+    /// more than fit in a `u64`.
+    ///
+    /// Works up from the last row, where each position is a single timeline,
+    /// so every row's counts come from the row below it. Counts that overflow
+    /// are kept as `None`, which is only an error if the start reaches one.
+    fn simulate_quantum(&self) -> Result<u64, ManifoldError> {
+        let start_pos = self
+            .grid
+            .first()
+            .and_then(|row| row.iter().position(|s| *s == SpaceState::Start))
+            .ok_or(ManifoldError::MissingStart)?;
+        let width = self.grid[0].len();
 
-    fn help_sim_quantum(
-        &self,
-        particle_pos: (usize, usize),
-        memo: &mut HashMap<(usize, usize), u64>,
-    ) -> u64 {
-        let (y, x) = particle_pos;
-        if memo.contains_key(&particle_pos) {
-            memo[&particle_pos]
-        } else if y == self.grid.len() - 1 {
-            memo.insert(particle_pos, 1);
-            1
-        } else {
-            let mut res = 0;
-            match self.grid[y + 1][x] {
-                SpaceState::Empty => {
-                    res += self.help_sim_quantum((y + 1, x), memo);
-                }
-                SpaceState::Splitter => {
-                    if x != 0 {
-                        res += self.help_sim_quantum((y + 1, x - 1), memo);
+        let mut below = vec![Some(1u64); width];
+        for next_row in self.grid[1..].iter().rev() {
+            below = (0..width)
+                .map(|x| match next_row[x] {
+                    SpaceState::Empty => below[x],
+                    SpaceState::Splitter => {
+                        let left = if x != 0 { below[x - 1]? } else { 0 };
+                        let right = if x != width - 1 { below[x + 1]? } else { 0 };
+                        left.checked_add(right)
                     }
-                    if x != self.grid[y].len() - 1 {
-                        res += self.help_sim_quantum((y + 1, x + 1), memo);
-                    }
-                }
-                SpaceState::Start => {
-                    // this should never happen
-                }
-                SpaceState::Beam => {
-                    // this should never happen
-                }
-            }
-
-            memo.insert(particle_pos, res);
-            res
+                    // Particles stop if they run into anything else
+                    SpaceState::Start | SpaceState::Beam => Some(0),
+                })
+                .collect();
         }
+        below[start_pos].ok_or(ManifoldError::Overflow)
     }
 }
 
@@ -142,24 +123,141 @@ fn parse_space(input: &str) -> IResult<&str, SpaceState> {
 }
 
 fn parse_row(input: &str) -> IResult<&str, Vec<SpaceState>> {
-    many1(parse_space).parse(input)
+    terminated(many1(parse_space), eof).parse(input)
 }
 
-fn main() {
-    let args = Args::parse();
+enum ManifoldError {
+    InvalidSpace {
+        line: usize,
+        column: usize,
+    },
+    Ragged {
+        line: usize,
+        width: usize,
+        expected: usize,
+    },
+    MissingStart,
+    Overflow,
+}
 
-    let input_file = File::open(args.input).expect("Could not find file");
-    let reader = BufReader::new(input_file);
+impl fmt::Display for ManifoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManifoldError::InvalidSpace { line, column } => {
+                write!(f, "Could not parse line {}, column {}", line, column)
+            }
+            ManifoldError::Ragged {
+                line,
+                width,
+                expected,
+            } => write!(
+                f,
+                "Line {} is {} spaces wide but the first line is {}",
+                line, width, expected
+            ),
+            ManifoldError::MissingStart => write!(f, "Start was not on the first row"),
+            ManifoldError::Overflow => write!(f, "Too many timelines to count"),
+        }
+    }
+}
 
+struct Solution {
+    splits: u64,
+    ends: u64,
+}
+
+fn solve(input: &str) -> Result<Solution, ManifoldError> {
     let mut grid = Grid::default();
-    for line in reader.lines() {
-        let line = line.expect("Could not read line");
-        let (_, row) = parse_row(&line).expect("Could not parse line");
+    for (line_num, line) in input.lines().enumerate() {
+        let row = match parse_row(line) {
+            Ok((_, row)) => row,
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+                let parsed = &line[..line.len() - e.input.len()];
+                return Err(ManifoldError::InvalidSpace {
+                    line: line_num + 1,
+                    column: parsed.chars().count() + 1,
+                });
+            }
+            Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never need more input"),
+        };
+        if let Some(first) = grid.grid.first()
+            && first.len() != row.len()
+        {
+            return Err(ManifoldError::Ragged {
+                line: line_num + 1,
+                width: row.len(),
+                expected: first.len(),
+            });
+        }
         grid.add_row(row);
     }
 
+    let ends = grid.simulate_quantum()?;
     let sim_res = grid.simulate();
-    let quantum_sim = grid.simulate_quantum();
-    println!("Splits: {}", sim_res.splits);
-    println!("Ends: {}", quantum_sim);
+    Ok(Solution {
+        splits: sim_res.splits,
+        ends,
+    })
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let input = args.load_input(7).expect("Could not load input");
+
+    let solution = solve(&input).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    println!("Splits: {}", solution.splits);
+    println!("Ends: {}", solution.ends);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn rejects_ragged_grids() {
+        assert!(matches!(
+            solve("..S..\n.\n"),
+            Err(ManifoldError::Ragged { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_start() {
+        assert!(matches!(
+            solve("...\n...\n"),
+            Err(ManifoldError::MissingStart)
+        ));
+    }
+
+    #[test]
+    fn rejects_too_many_timelines() {
+        let input = format!("....S....\n{}", "^^^^^^^^^\n".repeat(80));
+        assert!(matches!(solve(&input), Err(ManifoldError::Overflow)));
+    }
+
+    #[test]
+    fn deep_grids_do_not_overflow_the_stack() {
+        let input = format!("S\n{}", ".\n".repeat(300_000));
+        assert!(matches!(solve(&input), Ok(Solution { ends: 1, .. })));
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        let options = generate::GridOptions {
+            width: 31,
+            height: 30,
+            density: 0.5,
+        };
+        for seed in 0..5 {
+            let input = generate::day7(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            assert!(solve(&input).is_ok(), "seed {}", seed);
+        }
+    }
 }
@@ -1,9 +1,4 @@
-use std::{
-    cmp::Reverse,
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::{collections::HashSet, fmt, process};
 
 use advent_of_code_2025::Args;
 use clap::Parser;
@@ -14,12 +9,16 @@ use nom::{
 
 struct Junction {
     loc: (i64, i64, i64),
-    id: usize,
     circuit: usize,
 }
 
-fn distance(p1: (i64, i64, i64), p2: (i64, i64, i64)) -> f64 {
-    f64::sqrt(((p1.0 - p2.0).pow(2) + (p1.1 - p2.1).pow(2) + (p1.2 - p2.2).pow(2)) as f64)
+/// The exact squared distance between two points, or `None` if it does not
+/// fit in a `u128`
+fn squared_distance(p1: (i64, i64, i64), p2: (i64, i64, i64)) -> Option<u128> {
+    let square = |a: i64, b: i64| (a as i128 - b as i128).unsigned_abs().pow(2);
+    square(p1.0, p2.0)
+        .checked_add(square(p1.1, p2.1))?
+        .checked_add(square(p1.2, p2.2))
 }
 
 fn parse_junction(input: &str, id: usize) -> IResult<&str, Junction> {
@@ -27,41 +26,53 @@ fn parse_junction(input: &str, id: usize) -> IResult<&str, Junction> {
         separated_pair(i64, tag(","), separated_pair(i64, tag(","), i64)),
         |(a, (b, c))| Junction {
             loc: (a, b, c),
-            id: id,
             circuit: id,
         },
     )
     .parse(input)
 }
 
-fn main() {
-    let args = Args::parse();
+enum JunctionError {
+    InvalidJunction { line: usize },
+    TooFewJunctions,
+    TooFarApart,
+}
 
-    let input_file = File::open(args.input).expect("Could not find file");
-    let reader = BufReader::new(input_file);
+impl fmt::Display for JunctionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JunctionError::InvalidJunction { line } => {
+                write!(f, "Could not parse junction on line {}", line)
+            }
+            JunctionError::TooFewJunctions => write!(f, "At least two junctions are needed"),
+            JunctionError::TooFarApart => write!(f, "Junctions are too far apart to compare"),
+        }
+    }
+}
 
-    let mut junctions: Vec<Junction> = reader
-        .lines()
-        .enumerate()
-        .map(|(i, line)| {
-            parse_junction(&line.expect("Could not read line"), i)
-                .expect("Could not parse line")
-                .1
-        })
-        .collect();
+/// Connects the closest junctions until they form a single circuit and
+/// returns the product of the x coordinates of the last two connected
+fn solve(input: &str) -> Result<i128, JunctionError> {
+    let mut junctions = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        match parse_junction(line, i) {
+            Ok(("", junction)) => junctions.push(junction),
+            _ => return Err(JunctionError::InvalidJunction { line: i + 1 }),
+        }
+    }
+    if junctions.len() < 2 {
+        return Err(JunctionError::TooFewJunctions);
+    }
 
     let mut possible_edges = Vec::new();
     for i in 0..junctions.len() {
-        for j in 0..junctions.len() {
-            if i != j && i < j {
-                possible_edges.push((i, j));
-            }
+        for j in i + 1..junctions.len() {
+            let distance = squared_distance(junctions[i].loc, junctions[j].loc)
+                .ok_or(JunctionError::TooFarApart)?;
+            possible_edges.push((distance, i, j));
         }
     }
-    possible_edges.sort_by(|(a, b), (c, d)| {
-        distance(junctions[*a].loc, junctions[*b].loc)
-            .total_cmp(&distance(junctions[*c].loc, junctions[*d].loc))
-    });
+    possible_edges.sort_unstable();
 
     let mut circuits: Vec<HashSet<usize>> = junctions
         .iter()
@@ -73,10 +84,7 @@ fn main() {
         })
         .collect();
 
-    let mut merge_idx = 0;
-    // for part 1, stop after 1000 connections
-    loop {
-        let (a, b) = possible_edges[merge_idx];
+    for (_, a, b) in possible_edges {
         let a_circuit = junctions[a].circuit;
         let b_circuit = junctions[b].circuit;
         if a_circuit != b_circuit {
@@ -85,23 +93,64 @@ fn main() {
                 .for_each(|ji| junctions[*ji].circuit = a_circuit);
             circuits[a_circuit] = circuits[a_circuit]
                 .union(&circuits[b_circuit])
-                .map(|&id| id)
+                .copied()
                 .collect();
             circuits[b_circuit].clear();
 
             if circuits[a_circuit].len() == junctions.len() {
-                println!(
-                    "Product of x of last two junctions: {}",
-                    junctions[a].loc.0 * junctions[b].loc.0
-                );
-                break;
+                return Ok(junctions[a].loc.0 as i128 * junctions[b].loc.0 as i128);
             }
         }
-        merge_idx += 1;
     }
 
-    // part 1
-    // circuits.sort_by_key(|c| Reverse(c.len()));
-    // let largest_3_prod: usize = circuits.iter().take(3).map(|c| c.len()).product();
-    // println!("largest 3 circuit product: {}", largest_3_prod);
+    unreachable!("connecting every pair of junctions forms a single circuit")
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let input = args.load_input(8).expect("Could not load input");
+
+    let product = solve(&input).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    println!("Product of x of last two junctions: {}", product);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn rejects_a_single_junction() {
+        assert!(matches!(
+            solve("1,2,3\n"),
+            Err(JunctionError::TooFewJunctions)
+        ));
+    }
+
+    #[test]
+    fn extreme_coordinates_do_not_overflow() {
+        let input = format!("{},0,0\n{},0,0\n", i64::MAX, i64::MIN);
+        assert_eq!(
+            solve(&input).ok(),
+            Some(i64::MAX as i128 * i64::MIN as i128)
+        );
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        let options = generate::Day8Options {
+            junctions: 50,
+            max_coord: 1000,
+        };
+        for seed in 0..5 {
+            let input = generate::day8(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            assert!(solve(&input).is_ok(), "seed {}", seed);
+        }
+    }
 }
@@ -1,43 +1,51 @@
 use std::{
     cmp,
+    collections::HashMap,
+    fmt,
     fs::File,
-    io::{BufRead, BufReader},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    process,
 };
 
 use advent_of_code_2025::Args;
 use clap::Parser;
 use nom::{
-    IResult, Parser as _, bytes::complete::tag, character::complete::usize,
-    sequence::separated_pair,
+    IResult, Parser as _, bytes::complete::tag, character::complete::i64, sequence::separated_pair,
 };
 
-fn parse_tile(input: &str) -> IResult<&str, (usize, usize)> {
-    separated_pair(usize, tag(","), usize).parse(input)
+#[derive(Parser)]
+#[command(about)]
+struct Day9Args {
+    #[command(flatten)]
+    args: Args,
+
+    /// draw the polygon and the best rectangles to this SVG file
+    #[arg(long)]
+    svg: Option<PathBuf>,
+
+    /// list this many of the biggest rectangles for each criterion
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+    top: u32,
 }
 
-fn area(a: (usize, usize), b: (usize, usize)) -> usize {
-    let x = if b.0 > a.0 {
-        b.0 - a.0 + 1
-    } else {
-        a.0 - b.0 + 1
-    };
-    let y = if b.1 > a.1 {
-        b.1 - a.1 + 1
-    } else {
-        a.1 - b.1 + 1
-    };
-    x * y
+type Tile = (i64, i64);
+/// The two ends of an edge, with the lower coordinate first
+type Edge = (Tile, Tile);
+
+fn parse_tile(input: &str) -> IResult<&str, Tile> {
+    separated_pair(i64, tag(","), i64).parse(input)
 }
 
-struct Rect {
-    lo_x: usize,
-    hi_x: usize,
-    lo_y: usize,
-    hi_y: usize,
+struct Rect<T> {
+    lo_x: T,
+    hi_x: T,
+    lo_y: T,
+    hi_y: T,
 }
 
-impl Rect {
-    fn from(a: (usize, usize), b: (usize, usize)) -> Rect {
+impl<T: Ord + Copy> Rect<T> {
+    fn from(a: (T, T), b: (T, T)) -> Rect<T> {
         Rect {
             lo_x: cmp::min(a.0, b.0),
             hi_x: cmp::max(a.0, b.0),
@@ -47,98 +55,731 @@ impl Rect {
     }
 }
 
-fn is_inner(
-    a: (usize, usize),
-    b: (usize, usize),
-    hori_edges: &Vec<((usize, usize), (usize, usize))>,
-    vert_edges: &Vec<((usize, usize), (usize, usize))>,
-) -> bool {
-    let rect = Rect::from(a, b);
+impl<T: Copy + Into<i128>> Rect<T> {
+    /// The number of tiles in each row, which fits even when the corners are
+    /// at opposite ends of `i64`
+    fn width(&self) -> u128 {
+        (self.hi_x.into() - self.lo_x.into()) as u128 + 1
+    }
+
+    fn height(&self) -> u128 {
+        (self.hi_y.into() - self.lo_y.into()) as u128 + 1
+    }
+
+    /// The number of tiles covered, or `None` if that does not fit in a `u128`
+    fn area(&self) -> Option<u128> {
+        self.width().checked_mul(self.height())
+    }
+}
+
+#[derive(Debug)]
+enum Orientation {
+    Clockwise,
+    CounterClockwise,
+}
+
+/// Why the red tiles do not form a simple rectilinear polygon. Lines are the
+/// 1-based input lines of the tiles involved.
+enum PolygonError {
+    TooFewTiles(usize),
+    TooLarge,
+    NotAxisAligned {
+        line: usize,
+        next_line: usize,
+    },
+    DuplicateTile {
+        line: usize,
+        other_line: usize,
+    },
+    SelfIntersection {
+        edge: (usize, usize),
+        other_edge: (usize, usize),
+    },
+}
+
+impl fmt::Display for PolygonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolygonError::TooFewTiles(count) => write!(
+                f,
+                "A rectilinear polygon needs at least 4 red tiles, got {}",
+                count
+            ),
+            PolygonError::TooLarge => {
+                write!(f, "The red tiles are spread too far apart to measure areas")
+            }
+            PolygonError::NotAxisAligned { line, next_line } => write!(
+                f,
+                "Red tiles on lines {} and {} share neither a row nor a column",
+                line, next_line
+            ),
+            PolygonError::DuplicateTile { line, other_line } => write!(
+                f,
+                "Red tile on line {} repeats the one on line {}",
+                other_line, line
+            ),
+            PolygonError::SelfIntersection { edge, other_edge } => write!(
+                f,
+                "Edge between lines {} and {} crosses the edge between lines {} and {}",
+                edge.0, edge.1, other_edge.0, other_edge.1
+            ),
+        }
+    }
+}
+
+/// An edge along with the indices of the tiles it joins
+struct IndexedEdge {
+    edge: Edge,
+    tiles: (usize, usize),
+    vertical: bool,
+}
+
+impl IndexedEdge {
+    fn touches(&self, other: &IndexedEdge) -> bool {
+        let ((a_lo_x, a_lo_y), (a_hi_x, a_hi_y)) = self.edge;
+        let ((b_lo_x, b_lo_y), (b_hi_x, b_hi_y)) = other.edge;
+        a_lo_x <= b_hi_x && b_lo_x <= a_hi_x && a_lo_y <= b_hi_y && b_lo_y <= a_hi_y
+    }
+
+    /// Whether two collinear edges sharing a corner overlap past it, rather
+    /// than running straight on through it
+    fn folds_back(&self, other: &IndexedEdge) -> bool {
+        self.edge.1 != other.edge.0 && other.edge.1 != self.edge.0
+    }
+}
+
+/// A simple rectilinear polygon through the red tiles, in input order
+struct Polygon {
+    hori_edges: Vec<Edge>,
+    vert_edges: Vec<Edge>,
+    orientation: Orientation,
+    /// The area enclosed by the lines through the tile centres
+    area: u128,
+}
+
+impl Polygon {
+    /// Checks that consecutive tiles share a row or column, that no tile is
+    /// repeated and that no two edges meet except at their shared corner
+    fn new(red_tiles: &[Tile]) -> Result<Polygon, PolygonError> {
+        let n = red_tiles.len();
+        if n < 4 {
+            return Err(PolygonError::TooFewTiles(n));
+        }
 
-    for ((x1, y), (x2, _)) in hori_edges {
-        if rect.lo_y < *y && *y < rect.hi_y {
-            if *x1 < rect.lo_x && *x2 > rect.hi_x {
-                return false;
+        let mut seen = HashMap::new();
+        for (i, tile) in red_tiles.iter().enumerate() {
+            if let Some(first) = seen.insert(*tile, i) {
+                return Err(PolygonError::DuplicateTile {
+                    line: first + 1,
+                    other_line: i + 1,
+                });
             }
+        }
 
-            if *x2 > rect.lo_x && *x1 < rect.hi_x {
-                return false;
+        let mut edges = Vec::with_capacity(n);
+        for (i, cur_tile) in red_tiles.iter().enumerate() {
+            let next = (i + 1) % n;
+            let next_tile = red_tiles[next];
+            let vertical = if cur_tile.0 == next_tile.0 {
+                true
+            } else if cur_tile.1 == next_tile.1 {
+                false
+            } else {
+                return Err(PolygonError::NotAxisAligned {
+                    line: i + 1,
+                    next_line: next + 1,
+                });
+            };
+            edges.push(IndexedEdge {
+                edge: (
+                    (
+                        cmp::min(cur_tile.0, next_tile.0),
+                        cmp::min(cur_tile.1, next_tile.1),
+                    ),
+                    (
+                        cmp::max(cur_tile.0, next_tile.0),
+                        cmp::max(cur_tile.1, next_tile.1),
+                    ),
+                ),
+                tiles: (i, next),
+                vertical,
+            });
+        }
+
+        for (i, edge) in edges.iter().enumerate() {
+            for other in &edges[i + 1..] {
+                let adjacent = edge.tiles.1 == other.tiles.0 || other.tiles.1 == edge.tiles.0;
+                // Neighbouring edges always share a corner, so they only
+                // intersect if they fold back along the same line
+                let intersects = if adjacent {
+                    edge.vertical == other.vertical && edge.folds_back(other)
+                } else {
+                    edge.touches(other)
+                };
+                if intersects {
+                    return Err(PolygonError::SelfIntersection {
+                        edge: (edge.tiles.0 + 1, edge.tiles.1 + 1),
+                        other_edge: (other.tiles.0 + 1, other.tiles.1 + 1),
+                    });
+                }
             }
         }
+
+        // Every rectangle fits in the bounding box, so checking its area once
+        // means no candidate's area can overflow
+        let min_tile = red_tiles
+            .iter()
+            .fold(red_tiles[0], |m, t| (m.0.min(t.0), m.1.min(t.1)));
+        let max_tile = red_tiles
+            .iter()
+            .fold(red_tiles[0], |m, t| (m.0.max(t.0), m.1.max(t.1)));
+        if Rect::from(min_tile, max_tile).area().is_none() {
+            return Err(PolygonError::TooLarge);
+        }
+
+        // Shoelace formula; positive when counter-clockwise with y pointing up
+        let doubled_area = red_tiles
+            .iter()
+            .zip(red_tiles.iter().cycle().skip(1))
+            .try_fold(0i128, |sum, (a, b)| {
+                let cross = (a.0 as i128).checked_mul(b.1 as i128)?
+                    - (b.0 as i128).checked_mul(a.1 as i128)?;
+                sum.checked_add(cross)
+            })
+            .ok_or(PolygonError::TooLarge)?;
+        let orientation = if doubled_area > 0 {
+            Orientation::CounterClockwise
+        } else {
+            Orientation::Clockwise
+        };
+
+        let (vert_edges, hori_edges): (Vec<IndexedEdge>, Vec<IndexedEdge>) =
+            edges.into_iter().partition(|e| e.vertical);
+        Ok(Polygon {
+            hori_edges: hori_edges.into_iter().map(|e| e.edge).collect(),
+            vert_edges: vert_edges.into_iter().map(|e| e.edge).collect(),
+            orientation,
+            area: doubled_area.unsigned_abs() / 2,
+        })
     }
+}
+
+/// The polygon's tiles on a compressed grid, where each cell stands for a
+/// block of tiles that are all inside or all outside
+struct CompressedGrid {
+    /// The column and row of the cell each red tile starts, in input order
+    corners: Vec<(usize, usize)>,
+    /// `outside[j][i]` counts the outside cells in columns `..i` of rows `..j`
+    outside: Vec<Vec<u32>>,
+}
+
+impl CompressedGrid {
+    fn new(red_tiles: &[Tile], polygon: &Polygon) -> CompressedGrid {
+        // Each vertex starts a block and the tile after it starts the next one,
+        // so a block never straddles an edge. The first tile of each block is
+        // kept, and the last entry is one past the polygon so it starts no
+        // block. Widened so the tile after `i64::MAX` can still be named.
+        let mut xs: Vec<i128> = red_tiles
+            .iter()
+            .flat_map(|t| [t.0 as i128, t.0 as i128 + 1])
+            .collect();
+        let mut ys: Vec<i128> = red_tiles
+            .iter()
+            .flat_map(|t| [t.1 as i128, t.1 as i128 + 1])
+            .collect();
+        xs.sort_unstable();
+        xs.dedup();
+        ys.sort_unstable();
+        ys.dedup();
+        let col = |x: i64| {
+            xs.binary_search(&(x as i128))
+                .expect("vertex x is compressed")
+        };
+        let row = |y: i64| {
+            ys.binary_search(&(y as i128))
+                .expect("vertex y is compressed")
+        };
+
+        let width = xs.len() - 1;
+        let height = ys.len() - 1;
+        let mut inside = vec![vec![false; width]; height];
+        for ((x1, y), (x2, _)) in &polygon.hori_edges {
+            inside[row(*y)][col(*x1)..=col(*x2)].fill(true);
+        }
+        for ((x, y1), (_, y2)) in &polygon.vert_edges {
+            for cells in &mut inside[row(*y1)..=row(*y2)] {
+                cells[col(*x)] = true;
+            }
+        }
 
-    for ((x, y1), (_, y2)) in vert_edges {
-        if rect.lo_x < *x && *x < rect.hi_x {
-            if *y1 < rect.lo_y && *y2 > rect.hi_y {
-                return false;
+        // Cast a ray left from each cell: it is inside if it crosses an odd
+        // number of vertical edges, counting each edge's span half-open
+        for (j, cells) in inside.iter_mut().enumerate() {
+            let y = ys[j];
+            let mut crossings = vec![false; width];
+            for ((x, y1), (_, y2)) in &polygon.vert_edges {
+                if (*y1 as i128) <= y && y < (*y2 as i128) {
+                    crossings[col(*x)] ^= true;
+                }
+            }
+            let mut parity = false;
+            for (cell, crossing) in cells.iter_mut().zip(crossings) {
+                if crossing {
+                    parity = !parity;
+                }
+                *cell |= parity;
             }
+        }
 
-            if *y2 > rect.lo_y && *y1 < rect.hi_y {
-                return false;
+        let mut outside = vec![vec![0; width + 1]; height + 1];
+        for j in 0..height {
+            for i in 0..width {
+                outside[j + 1][i + 1] = outside[j][i + 1] + outside[j + 1][i] - outside[j][i]
+                    + u32::from(!inside[j][i]);
             }
         }
+
+        let corners = red_tiles.iter().map(|&(x, y)| (col(x), row(y))).collect();
+        CompressedGrid { corners, outside }
     }
 
-    true
+    /// Whether every tile of the rectangle with red tiles `a` and `b` in
+    /// opposite corners is on or inside the polygon, in constant time
+    fn is_inner(&self, a: usize, b: usize) -> bool {
+        let ((a_i, a_j), (b_i, b_j)) = (self.corners[a], self.corners[b]);
+        let (lo_i, hi_i) = (a_i.min(b_i), a_i.max(b_i) + 1);
+        let (lo_j, hi_j) = (a_j.min(b_j), a_j.max(b_j) + 1);
+
+        let o = &self.outside;
+        o[hi_j][hi_i] + o[lo_j][lo_i] - o[lo_j][hi_i] - o[hi_j][lo_i] == 0
+    }
 }
 
-fn main() {
-    let args = Args::parse();
+/// A red tile and the 1-based input line it was read from
+#[derive(Clone, Copy)]
+struct Corner {
+    tile: Tile,
+    line: usize,
+}
 
-    let file = File::open(args.input).expect("Could not open file");
-    let reader = BufReader::new(file);
+impl fmt::Display for Corner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{}) line {}", self.tile.0, self.tile.1, self.line)
+    }
+}
 
-    let red_tiles: Vec<(usize, usize)> = reader
-        .lines()
-        .map(|line| {
-            parse_tile(&line.expect("Could not read line"))
-                .expect("Could not parse tile")
-                .1
-        })
-        .collect();
+/// A rectangle with red tiles in two opposite corners
+struct Candidate {
+    corners: [Corner; 2],
+    area: u128,
+}
 
-    // Get borders
-    let mut hori_edges: Vec<((usize, usize), (usize, usize))> = Vec::new();
-    let mut vert_edges: Vec<((usize, usize), (usize, usize))> = Vec::new();
-    for (i, cur_tile) in red_tiles.iter().enumerate() {
-        let next_tile = if i < red_tiles.len() - 1 {
-            red_tiles[i + 1]
-        } else {
-            red_tiles[0]
-        };
+/// The biggest rectangles, largest first, with and without the requirement
+/// that every tile is red or green
+struct Rankings {
+    biggest: Vec<Candidate>,
+    biggest_inner: Vec<Candidate>,
+}
 
-        if cur_tile.0 == next_tile.0 {
-            vert_edges.push((
-                (cur_tile.0, cmp::min(cur_tile.1, next_tile.1)),
-                (cur_tile.0, cmp::max(cur_tile.1, next_tile.1)),
-            ));
+/// Ranks every pair of red tiles by the area of their rectangle and keeps the
+/// `top` biggest overall and the `top` biggest inside the polygon. Ties keep
+/// input order.
+fn rank_rectangles(red_tiles: &[Tile], grid: &CompressedGrid, top: usize) -> Rankings {
+    let mut pairs = Vec::new();
+    for i in 0..red_tiles.len() {
+        for j in i + 1..red_tiles.len() {
+            let area = Rect::from(red_tiles[i], red_tiles[j])
+                .area()
+                .expect("Rectangles fit in the bounding box checked by Polygon::new");
+            pairs.push((area, i, j));
+        }
+    }
+    pairs.sort_by_key(|&(area, _, _)| cmp::Reverse(area));
+
+    let candidate = |&(area, i, j): &(u128, usize, usize)| Candidate {
+        corners: [i, j].map(|k| Corner {
+            tile: red_tiles[k],
+            line: k + 1,
+        }),
+        area,
+    };
+    Rankings {
+        biggest: pairs.iter().take(top).map(candidate).collect(),
+        biggest_inner: pairs
+            .iter()
+            .filter(|&&(_, i, j)| grid.is_inner(i, j))
+            .take(top)
+            .map(candidate)
+            .collect(),
+    }
+}
+
+fn print_candidates(title: &str, candidates: &[Candidate]) {
+    println!(
+        "{}: {}",
+        title,
+        candidates.first().map_or(0, |candidate| candidate.area)
+    );
+    for (rank, candidate) in candidates.iter().enumerate() {
+        let [a, b] = &candidate.corners;
+        if candidates.len() > 1 {
+            println!("  {}. {} to {}, area {}", rank + 1, a, b, candidate.area);
         } else {
-            hori_edges.push((
-                (cmp::min(cur_tile.0, next_tile.0), cur_tile.1),
-                (cmp::max(cur_tile.0, next_tile.0), cur_tile.1),
-            ));
+            println!("  {} to {}", a, b);
         }
     }
+}
 
-    let mut biggest_area = 0;
-    let mut biggest_inner_area = 0;
-    for i in 0..red_tiles.len() {
-        for j in 0..red_tiles.len() {
-            if i < j {
-                let cur_area = area(red_tiles[i], red_tiles[j]);
-                if cur_area > biggest_area {
-                    biggest_area = cur_area;
-                }
+/// Draws the polygon filled green over red corner tiles, then outlines each
+/// candidate rectangle in its colour and labels its corners
+fn write_svg(
+    path: &Path,
+    red_tiles: &[Tile],
+    rects: &[(&str, &str, &Candidate)],
+) -> std::io::Result<()> {
+    let min_x = red_tiles.iter().map(|t| t.0).min().unwrap_or(0);
+    let max_x = red_tiles.iter().map(|t| t.0).max().unwrap_or(0);
+    let min_y = red_tiles.iter().map(|t| t.1).min().unwrap_or(0);
+    let max_y = red_tiles.iter().map(|t| t.1).max().unwrap_or(0);
+    let (width, height) = (
+        max_x as f64 - min_x as f64 + 1.0,
+        max_y as f64 - min_y as f64 + 1.0,
+    );
+    let extent = width.max(height);
+    let margin = (extent / 20.0).max(1.0);
+    let font_size = (extent / 60.0).max(0.5);
 
-                if cur_area > biggest_inner_area
-                    && is_inner(red_tiles[i], red_tiles[j], &hori_edges, &vert_edges)
-                {
-                    biggest_inner_area = cur_area;
-                }
-            }
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+        min_x as f64 - margin,
+        min_y as f64 - margin,
+        width + 2.0 * margin,
+        height + 2.0 * margin
+    )?;
+
+    let points: Vec<String> = red_tiles
+        .iter()
+        .map(|t| format!("{},{}", t.0 as f64 + 0.5, t.1 as f64 + 0.5))
+        .collect();
+    writeln!(
+        out,
+        r#"<polygon points="{}" fill="green" fill-opacity="0.5" stroke="green" stroke-width="1" vector-effect="non-scaling-stroke"/>"#,
+        points.join(" ")
+    )?;
+    for tile in red_tiles {
+        writeln!(
+            out,
+            r#"<rect x="{}" y="{}" width="1" height="1" fill="red"/>"#,
+            tile.0, tile.1
+        )?;
+    }
+
+    for (label, colour, candidate) in rects {
+        let [a, b] = candidate.corners;
+        let rect = Rect::from(a.tile, b.tile);
+        writeln!(
+            out,
+            r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}" stroke-width="2" vector-effect="non-scaling-stroke"><title>{}</title></rect>"#,
+            rect.lo_x,
+            rect.lo_y,
+            rect.width(),
+            rect.height(),
+            colour,
+            label
+        )?;
+        for corner in [a, b] {
+            writeln!(
+                out,
+                r#"<text x="{}" y="{}" font-size="{}" fill="{}">{} {}</text>"#,
+                corner.tile.0, corner.tile.1, font_size, colour, label, corner
+            )?;
+        }
+    }
+
+    writeln!(out, "</svg>")?;
+    out.flush()
+}
+
+enum TileError {
+    InvalidTile { line: usize },
+    Polygon(PolygonError),
+}
+
+impl fmt::Display for TileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TileError::InvalidTile { line } => write!(f, "Could not parse tile on line {}", line),
+            TileError::Polygon(e) => e.fmt(f),
+        }
+    }
+}
+
+struct Solution {
+    red_tiles: Vec<Tile>,
+    polygon: Polygon,
+    rankings: Rankings,
+}
+
+fn solve(input: &str, top: usize) -> Result<Solution, TileError> {
+    let mut red_tiles = Vec::new();
+    for (line_num, line) in input.lines().enumerate() {
+        match parse_tile(line) {
+            Ok(("", tile)) => red_tiles.push(tile),
+            _ => return Err(TileError::InvalidTile { line: line_num + 1 }),
         }
     }
 
-    println!("Biggest rectangle area: {}", biggest_area);
-    println!("Biggest inner area: {}", biggest_inner_area);
+    let polygon = Polygon::new(&red_tiles).map_err(TileError::Polygon)?;
+    let grid = CompressedGrid::new(&red_tiles, &polygon);
+    let rankings = rank_rectangles(&red_tiles, &grid, top);
+
+    Ok(Solution {
+        red_tiles,
+        polygon,
+        rankings,
+    })
+}
+
+fn main() {
+    let Day9Args { args, svg, top } = Day9Args::parse();
+
+    let input = args.load_input(9).expect("Could not load input");
+
+    let Solution {
+        red_tiles,
+        polygon,
+        rankings,
+    } = solve(&input, top as usize).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        process::exit(1);
+    });
+    println!("Orientation: {:?}", polygon.orientation);
+    println!("Polygon area: {}", polygon.area);
+
+    print_candidates("Biggest rectangle area", &rankings.biggest);
+    print_candidates("Biggest inner area", &rankings.biggest_inner);
+
+    if let Some(path) = svg {
+        let mut rects = Vec::new();
+        rects.extend(rankings.biggest.first().map(|c| ("biggest", "blue", c)));
+        rects.extend(
+            rankings
+                .biggest_inner
+                .first()
+                .map(|c| ("inner", "purple", c)),
+        );
+        write_svg(&path, &red_tiles, &rects).expect("Could not write SVG");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_of_code_2025::generate;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn rejects_unparsable_tiles() {
+        assert!(matches!(
+            solve("1,1\nx\n", 1),
+            Err(TileError::InvalidTile { line: 2 })
+        ));
+    }
+
+    fn polygon(input: &str) -> Result<Polygon, PolygonError> {
+        match solve(input, 1) {
+            Ok(solution) => Ok(solution.polygon),
+            Err(TileError::Polygon(e)) => Err(e),
+            Err(TileError::InvalidTile { .. }) => panic!("test tiles are valid"),
+        }
+    }
+
+    #[test]
+    fn reports_orientation_and_area() {
+        let Ok(square) = polygon("0,0\n0,10\n10,10\n10,0\n") else {
+            panic!("a square is a simple polygon");
+        };
+        assert!(matches!(square.orientation, Orientation::Clockwise));
+        assert_eq!(square.area, 100);
+
+        let Ok(square) = polygon("0,0\n10,0\n10,10\n0,10\n") else {
+            panic!("a square is a simple polygon");
+        };
+        assert!(matches!(square.orientation, Orientation::CounterClockwise));
+    }
+
+    #[test]
+    fn accepts_collinear_corners() {
+        let Ok(polygon) = polygon("0,0\n0,5\n0,10\n10,10\n10,0\n") else {
+            panic!("a straight run through a corner is still simple");
+        };
+        assert_eq!(polygon.area, 100);
+    }
+
+    #[test]
+    fn rejects_too_few_tiles() {
+        assert!(matches!(
+            polygon("0,0\n0,1\n1,1\n"),
+            Err(PolygonError::TooFewTiles(3))
+        ));
+    }
+
+    #[test]
+    fn rejects_tiles_too_far_apart() {
+        let (min, max) = (i64::MIN, i64::MAX);
+        let input = format!("{min},{min}\n{min},{max}\n{max},{max}\n{max},{min}\n");
+        assert!(matches!(polygon(&input), Err(PolygonError::TooLarge)));
+    }
+
+    #[test]
+    fn rejects_diagonal_edges() {
+        assert!(matches!(
+            polygon("0,0\n0,5\n5,5\n6,0\n"),
+            Err(PolygonError::NotAxisAligned {
+                line: 3,
+                next_line: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_duplicate_tiles() {
+        assert!(matches!(
+            polygon("0,0\n0,5\n5,5\n0,5\n"),
+            Err(PolygonError::DuplicateTile {
+                line: 2,
+                other_line: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_fold_backs() {
+        assert!(matches!(
+            polygon("0,0\n0,10\n0,5\n10,5\n10,0\n"),
+            Err(PolygonError::SelfIntersection {
+                edge: (1, 2),
+                other_edge: (2, 3)
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_crossing_edges() {
+        // A figure of eight whose middle edges cross
+        assert!(matches!(
+            polygon("0,0\n0,10\n10,10\n10,5\n-5,5\n-5,0\n"),
+            Err(PolygonError::SelfIntersection { .. })
+        ));
+    }
+
+    fn best_areas(input: &str) -> (u128, u128, [usize; 2]) {
+        let Ok(solution) = solve(input, 1) else {
+            panic!("test polygons are valid");
+        };
+        let Rankings {
+            biggest,
+            biggest_inner,
+        } = solution.rankings;
+        let inner = &biggest_inner[0];
+        (
+            biggest[0].area,
+            inner.area,
+            inner.corners.map(|corner| corner.line),
+        )
+    }
+
+    const EXAMPLE: &str = "7,1\n11,1\n11,7\n9,7\n9,5\n2,5\n2,3\n7,3\n";
+
+    #[test]
+    fn example() {
+        let (biggest, biggest_inner, _) = best_areas(EXAMPLE);
+        assert_eq!((biggest, biggest_inner), (50, 24));
+    }
+
+    #[test]
+    fn concave_polygon_skips_rectangles_outside_it() {
+        // A U opening downwards, so the biggest corner pair spans the notch
+        let input = "0,0\n0,10\n10,10\n10,0\n7,0\n7,8\n3,8\n3,0\n";
+        assert_eq!(best_areas(input), (121, 44, [2, 8]));
+    }
+
+    #[test]
+    fn svg_draws_the_polygon_and_rectangles() {
+        let Ok(solution) = solve(EXAMPLE, 1) else {
+            panic!("the example is a valid polygon");
+        };
+        let path = std::env::temp_dir().join(format!("aoc-day9-{}.svg", process::id()));
+        let inner = &solution.rankings.biggest_inner[0];
+        write_svg(&path, &solution.red_tiles, &[("inner", "purple", inner)]).unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="1 0 12 9">"#));
+        assert!(svg.contains(
+            r#"<polygon points="7.5,1.5 11.5,1.5 11.5,7.5 9.5,7.5 9.5,5.5 2.5,5.5 2.5,3.5 7.5,3.5""#
+        ));
+        assert_eq!(svg.matches(r#"fill="red""#).count(), 8);
+        assert!(
+            svg.contains(r#"<rect x="2" y="3" width="8" height="3" fill="none" stroke="purple""#)
+        );
+        assert!(svg.contains(r#"fill="purple">inner (9,5) line 5</text>"#));
+        assert!(svg.contains(r#"fill="purple">inner (2,3) line 7</text>"#));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn lists_the_top_rectangles_in_order() {
+        let Ok(solution) = solve(EXAMPLE, 3) else {
+            panic!("the example is a valid polygon");
+        };
+        let summary = |candidates: &[Candidate]| -> Vec<(Tile, Tile, u128)> {
+            candidates
+                .iter()
+                .map(|c| (c.corners[0].tile, c.corners[1].tile, c.area))
+                .collect()
+        };
+        // Equal areas keep input order
+        assert_eq!(
+            summary(&solution.rankings.biggest),
+            [
+                ((11, 1), (2, 5), 50),
+                ((11, 7), (2, 3), 50),
+                ((9, 7), (2, 3), 40)
+            ]
+        );
+        assert_eq!(
+            summary(&solution.rankings.biggest_inner),
+            [
+                ((9, 5), (2, 3), 24),
+                ((11, 1), (9, 7), 21),
+                ((2, 5), (7, 3), 18)
+            ]
+        );
+        let lines = solution.rankings.biggest_inner[0].corners.map(|c| c.line);
+        assert_eq!(lines, [5, 7]);
+    }
+
+    #[test]
+    fn areas_beyond_u64_are_exact() {
+        let n = 4_000_000_000_000_000_000i64;
+        let input = format!("{},{}\n{},{}\n{},{}\n{},{}\n", -n, -n, -n, n, n, n, n, -n);
+        let side = 2 * n as u128 + 1;
+        assert_eq!(best_areas(&input), (side * side, side * side, [1, 3]));
+    }
+
+    #[test]
+    fn solves_generated_inputs() {
+        let options = generate::Day9Options {
+            corners: 40,
+            max_coord: 1000,
+        };
+        for seed in 0..5 {
+            let input = generate::day9(&mut ChaCha8Rng::seed_from_u64(seed), &options);
+            assert!(solve(&input, 1).is_ok(), "seed {}", seed);
+        }
+    }
 }
@@ -0,0 +1,549 @@
+//! Random puzzle inputs for stress testing.
+//!
+//! Every generator takes its size knobs as a [`clap::Args`] struct so the
+//! runner can expose them directly, and draws everything from the `rng` it is
+//! given so that the same seed always produces the same input. The inputs are
+//! valid for each day's parser and, where a day needs it, solvable.
+
+use std::fmt::Write as _;
+
+use clap::Args;
+use rand::{
+    Rng,
+    seq::{IndexedRandom, SliceRandom},
+};
+
+/// Day 1: dial rotations
+#[derive(Args)]
+pub struct Day1Options {
+    /// how many rotations to emit
+    #[arg(long, default_value_t = 4000)]
+    pub rotations: usize,
+
+    /// the largest distance of a single rotation
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_distance: u64,
+}
+
+pub fn day1(rng: &mut impl Rng, options: &Day1Options) -> String {
+    let mut input = String::new();
+    for _ in 0..options.rotations {
+        let direction = if rng.random_bool(0.5) { 'L' } else { 'R' };
+        let distance = rng.random_range(1..=options.max_distance);
+        writeln!(input, "{}{}", direction, distance).unwrap();
+    }
+    input
+}
+
+/// Day 2: product ID ranges
+#[derive(Args)]
+pub struct Day2Options {
+    /// how many ranges to emit
+    #[arg(long, default_value_t = 35)]
+    pub ranges: usize,
+
+    /// the largest number of IDs in a single range
+    #[arg(long, default_value_t = 100_000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_width: u64,
+
+    /// the most digits a range start may have
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..=18))]
+    pub max_digits: u32,
+}
+
+pub fn day2(rng: &mut impl Rng, options: &Day2Options) -> String {
+    let ranges: Vec<String> = (0..options.ranges)
+        .map(|_| {
+            let digits = rng.random_range(1..=options.max_digits);
+            let start = rng.random_range(10u64.pow(digits - 1)..10u64.pow(digits));
+            let end = start.saturating_add(rng.random_range(0..options.max_width));
+            format!("{}-{}", start, end)
+        })
+        .collect();
+    ranges.join(",") + "\n"
+}
+
+/// Day 3: battery banks
+#[derive(Args)]
+pub struct Day3Options {
+    /// how many banks to emit
+    #[arg(long, default_value_t = 200)]
+    pub banks: usize,
+
+    /// how many batteries each bank holds
+    #[arg(long, default_value_t = 100, value_parser = clap::value_parser!(u32).range(1..))]
+    pub batteries: u32,
+}
+
+pub fn day3(rng: &mut impl Rng, options: &Day3Options) -> String {
+    let mut input = String::new();
+    for _ in 0..options.banks {
+        for _ in 0..options.batteries {
+            input.push(char::from(b'0' + rng.random_range(1..=9)));
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// A grid of `width` by `height` cells
+#[derive(Args)]
+pub struct GridOptions {
+    /// the number of columns
+    #[arg(long, default_value_t = 140, value_parser = clap::value_parser!(u32).range(1..))]
+    pub width: u32,
+
+    /// the number of rows
+    #[arg(long, default_value_t = 140, value_parser = clap::value_parser!(u32).range(1..))]
+    pub height: u32,
+
+    /// the chance of each cell being filled
+    #[arg(long, default_value_t = 0.6, value_parser = parse_probability)]
+    pub density: f64,
+}
+
+fn parse_probability(s: &str) -> Result<f64, String> {
+    let p: f64 = s.parse().map_err(|e| format!("{}", e))?;
+    if (0.0..=1.0).contains(&p) {
+        Ok(p)
+    } else {
+        Err(String::from("must be between 0 and 1"))
+    }
+}
+
+/// Day 4: paper rolls on a grid
+pub fn day4(rng: &mut impl Rng, options: &GridOptions) -> String {
+    let mut input = String::new();
+    for _ in 0..options.height {
+        for _ in 0..options.width {
+            input.push(if rng.random_bool(options.density) {
+                '@'
+            } else {
+                '.'
+            });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// Day 5: fresh ingredient ranges and available ingredients
+#[derive(Args)]
+pub struct Day5Options {
+    /// how many fresh ranges to emit
+    #[arg(long, default_value_t = 180)]
+    pub ranges: usize,
+
+    /// how many available ingredients to emit
+    #[arg(long, default_value_t = 1000)]
+    pub ingredients: usize,
+
+    /// the largest ingredient ID
+    #[arg(long, default_value_t = 500_000_000_000_000)]
+    pub max_id: u64,
+
+    /// the largest number of IDs in a single range
+    #[arg(long, default_value_t = 10_000_000_000_000, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_width: u64,
+}
+
+pub fn day5(rng: &mut impl Rng, options: &Day5Options) -> String {
+    let mut input = String::new();
+    for _ in 0..options.ranges {
+        let start = rng.random_range(0..=options.max_id);
+        let end = start.saturating_add(rng.random_range(0..options.max_width));
+        writeln!(input, "{}-{}", start, end.min(options.max_id)).unwrap();
+    }
+    input.push('\n');
+    for _ in 0..options.ingredients {
+        writeln!(input, "{}", rng.random_range(0..=options.max_id)).unwrap();
+    }
+    input
+}
+
+/// Day 6: a worksheet of math problems
+#[derive(Args)]
+pub struct Day6Options {
+    /// how many problems to emit
+    #[arg(long, default_value_t = 1000)]
+    pub problems: usize,
+
+    /// how many numbers each problem has, at most 4 so that products of
+    /// 4-digit numbers, across or down, stay well within 64 bits
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=4))]
+    pub rows: u32,
+
+    /// the most digits a number may have
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..=4))]
+    pub max_digits: u32,
+}
+
+/// Numbers are also read down the columns, so the digit counts in a problem
+/// rise and then fall to keep every column free of gaps
+pub fn day6(rng: &mut impl Rng, options: &Day6Options) -> String {
+    let rows = options.rows as usize;
+    let mut lines = vec![String::new(); rows + 1];
+    for problem in 0..options.problems {
+        let mut lengths: Vec<u32> = (0..rows)
+            .map(|_| rng.random_range(1..=options.max_digits))
+            .collect();
+        lengths.sort_unstable();
+        let (mut rising, mut falling) = (Vec::new(), Vec::new());
+        for length in lengths {
+            if rng.random_bool(0.5) {
+                rising.push(length);
+            } else {
+                falling.push(length);
+            }
+        }
+        let numbers: Vec<String> = rising
+            .into_iter()
+            .chain(falling.into_iter().rev())
+            .map(|digits| {
+                rng.random_range(10u32.pow(digits - 1)..10u32.pow(digits))
+                    .to_string()
+            })
+            .collect();
+        let width = numbers.iter().map(String::len).max().unwrap_or(1);
+        let left_aligned = rng.random_bool(0.5);
+        let operator = if rng.random_bool(0.5) { '+' } else { '*' };
+
+        if problem > 0 {
+            for line in &mut lines {
+                line.push(' ');
+            }
+        }
+        for (line, number) in lines.iter_mut().zip(&numbers) {
+            if left_aligned {
+                write!(line, "{:<width$}", number).unwrap();
+            } else {
+                write!(line, "{:>width$}", number).unwrap();
+            }
+        }
+        write!(lines[rows], "{:<width$}", operator).unwrap();
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Day 7: a tachyon manifold with the beam entering at the top
+pub fn day7(rng: &mut impl Rng, options: &GridOptions) -> String {
+    let width = options.width as usize;
+    let mut input = String::new();
+    for y in 0..options.height {
+        for x in 0..width {
+            input.push(if y == 0 && x == width / 2 {
+                'S'
+            } else if y > 0 && y % 2 == 0 && rng.random_bool(options.density) {
+                '^'
+            } else {
+                '.'
+            });
+        }
+        input.push('\n');
+    }
+    input
+}
+
+/// Day 8: junction boxes in space
+#[derive(Args)]
+pub struct Day8Options {
+    /// how many junction boxes to emit
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(2..))]
+    pub junctions: u32,
+
+    /// the largest coordinate on each axis
+    #[arg(long, default_value_t = 100_000)]
+    pub max_coord: u32,
+}
+
+pub fn day8(rng: &mut impl Rng, options: &Day8Options) -> String {
+    let mut input = String::new();
+    for _ in 0..options.junctions {
+        let mut coord = || rng.random_range(0..=options.max_coord);
+        writeln!(input, "{},{},{}", coord(), coord(), coord()).unwrap();
+    }
+    input
+}
+
+/// Day 9: the red tiles at the corners of a rectilinear polygon
+#[derive(Args)]
+pub struct Day9Options {
+    /// roughly how many corners the polygon has, rounded up to a multiple of 4
+    #[arg(long, default_value_t = 500, value_parser = clap::value_parser!(u32).range(4..))]
+    pub corners: u32,
+
+    /// the largest coordinate on each axis
+    #[arg(long, default_value_t = 100_000)]
+    pub max_coord: u32,
+}
+
+/// Builds the polygon from two skylines that share their column edges: one
+/// above the middle row and one below it. Neighbouring heights always differ,
+/// so no two corners coincide and no edges cross.
+pub fn day9(rng: &mut impl Rng, options: &Day9Options) -> String {
+    let columns = options.corners.div_ceil(4) as usize;
+    let max = options.max_coord.max(2 * columns as u32 + 4);
+    let mid = max / 2;
+
+    let mut xs = rand::seq::index::sample(rng, max as usize + 1, columns + 1).into_vec();
+    xs.sort_unstable();
+    let mut skyline = |range: std::ops::RangeInclusive<u32>| {
+        let mut heights: Vec<u32> = Vec::with_capacity(columns);
+        while heights.len() < columns {
+            let height = rng.random_range(range.clone());
+            if heights.last() != Some(&height) {
+                heights.push(height);
+            }
+        }
+        heights
+    };
+    let tops = skyline(mid + 1..=max);
+    let bottoms = skyline(0..=mid - 1);
+
+    let mut input = String::new();
+    for i in 0..columns {
+        writeln!(input, "{},{}", xs[i], tops[i]).unwrap();
+        writeln!(input, "{},{}", xs[i + 1], tops[i]).unwrap();
+    }
+    for i in (0..columns).rev() {
+        writeln!(input, "{},{}", xs[i + 1], bottoms[i]).unwrap();
+        writeln!(input, "{},{}", xs[i], bottoms[i]).unwrap();
+    }
+    input
+}
+
+/// Day 10: factory machines
+#[derive(Args)]
+pub struct Day10Options {
+    /// how many machines to emit
+    #[arg(long, default_value_t = 150)]
+    pub machines: usize,
+
+    /// the most indicator lights a machine may have
+    #[arg(long, default_value_t = 10, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_lights: u32,
+
+    /// the most buttons a machine may have
+    #[arg(long, default_value_t = 13, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_buttons: u32,
+
+    /// the largest joltage requirement
+    #[arg(long, default_value_t = 250)]
+    pub max_joltage: u64,
+}
+
+/// The target lights are the result of pressing a random subset of the
+/// buttons, so every machine can be initialized
+pub fn day10(rng: &mut impl Rng, options: &Day10Options) -> String {
+    let mut input = String::new();
+    for _ in 0..options.machines {
+        let lights = rng.random_range(1..=options.max_lights) as usize;
+        let buttons: Vec<Vec<usize>> = (0..rng.random_range(1..=options.max_buttons))
+            .map(|_| {
+                let wired = rng.random_range(1..=lights);
+                let mut button = rand::seq::index::sample(rng, lights, wired).into_vec();
+                button.sort_unstable();
+                button
+            })
+            .collect();
+
+        let mut target = vec![false; lights];
+        for button in &buttons {
+            if rng.random_bool(0.5) {
+                for &light in button {
+                    target[light] = !target[light];
+                }
+            }
+        }
+
+        input.push('[');
+        input.extend(target.iter().map(|&on| if on { '#' } else { '.' }));
+        input.push(']');
+        for button in &buttons {
+            let lights: Vec<String> = button.iter().map(usize::to_string).collect();
+            write!(input, " ({})", lights.join(",")).unwrap();
+        }
+        let joltages: Vec<String> = (0..lights)
+            .map(|_| rng.random_range(0..=options.max_joltage).to_string())
+            .collect();
+        writeln!(input, " {{{}}}", joltages.join(",")).unwrap();
+    }
+    input
+}
+
+/// Day 11: a reactor's device graph
+#[derive(Args)]
+pub struct Day11Options {
+    /// how many devices to emit, including the named ones
+    #[arg(long, default_value_t = 600, value_parser = clap::value_parser!(u32).range(5..=17576))]
+    pub devices: u32,
+
+    /// the most outputs a device may have
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_outputs: u32,
+}
+
+/// Devices only feed devices later in a random ordering, so the graph never
+/// has a cycle. `svr` and `you` come first, `dac` and `fft` somewhere in the
+/// middle and `out` last.
+pub fn day11(rng: &mut impl Rng, options: &Day11Options) -> String {
+    const NAMED: [&str; 5] = ["svr", "you", "dac", "fft", "out"];
+
+    let mut names: Vec<String> = (0..26 * 26 * 26)
+        .map(|i: u32| {
+            let letter = |n: u32| char::from(b'a' + (n % 26) as u8);
+            [letter(i / 676), letter(i / 26), letter(i)]
+                .into_iter()
+                .collect()
+        })
+        .filter(|name: &String| !NAMED.contains(&name.as_str()))
+        .collect();
+    names.shuffle(rng);
+    names.truncate(options.devices as usize - NAMED.len());
+
+    let len = names.len();
+    names.insert(rng.random_range(0..=len), String::from("dac"));
+    names.insert(rng.random_range(0..=len + 1), String::from("fft"));
+    names.insert(0, String::from("you"));
+    names.insert(0, String::from("svr"));
+    names.push(String::from("out"));
+
+    let mut input = String::new();
+    for (i, name) in names.iter().enumerate().take(names.len() - 1) {
+        let later = names.len() - i - 1;
+        let count = rng.random_range(1..=later.min(options.max_outputs as usize));
+        let outputs: Vec<&str> = rand::seq::index::sample(rng, later, count)
+            .into_iter()
+            .map(|j| names[i + 1 + j].as_str())
+            .collect();
+        writeln!(input, "{}: {}", name, outputs.join(" ")).unwrap();
+    }
+    input
+}
+
+/// Day 12: present shapes and the regions under the trees
+#[derive(Args)]
+pub struct Day12Options {
+    /// how many 3x3 shapes to emit
+    #[arg(long, default_value_t = 6, value_parser = clap::value_parser!(u32).range(1..))]
+    pub shapes: u32,
+
+    /// how many regions to emit
+    #[arg(long, default_value_t = 1000)]
+    pub regions: usize,
+
+    /// the largest width or height of a region
+    #[arg(long, default_value_t = 50, value_parser = clap::value_parser!(u32).range(3..))]
+    pub max_size: u32,
+
+    /// fill each region to 80-105% of its area, where the packing gets hard;
+    /// the solver may give up on regions this tight
+    #[arg(long)]
+    pub hard: bool,
+}
+
+/// Like the puzzle's own input, each region either has room for every present
+/// in its own 3x3 block or has more present cells than area, unless `hard` is
+/// set
+pub fn day12(rng: &mut impl Rng, options: &Day12Options) -> String {
+    let mut input = String::new();
+    let mut sizes = Vec::new();
+    for shape in 0..options.shapes {
+        let filled = rng.random_range(5..=7);
+        let cells = rand::seq::index::sample(rng, 9, filled).into_vec();
+        sizes.push(filled);
+        writeln!(input, "{}:", shape).unwrap();
+        for y in 0..3 {
+            for x in 0..3 {
+                input.push(if cells.contains(&(y * 3 + x)) {
+                    '#'
+                } else {
+                    '.'
+                });
+            }
+            input.push('\n');
+        }
+        input.push('\n');
+    }
+
+    for _ in 0..options.regions {
+        let width = rng.random_range(3..=options.max_size);
+        let height = rng.random_range(3..=options.max_size);
+        let area = (width * height) as usize;
+        let mut counts = vec![0; sizes.len()];
+        if options.hard {
+            let mut space = area * rng.random_range(80..=105) / 100;
+            loop {
+                let fitting: Vec<usize> = (0..sizes.len()).filter(|&s| sizes[s] <= space).collect();
+                let Some(&shape) = fitting.choose(rng) else {
+                    break;
+                };
+                counts[shape] += 1;
+                space -= sizes[shape];
+            }
+        } else if rng.random_bool(0.5) {
+            let blocks = (width / 3 * (height / 3)) as usize;
+            for _ in 0..rng.random_range(blocks / 2..=blocks) {
+                counts[rng.random_range(0..sizes.len())] += 1;
+            }
+        } else {
+            let mut cells = 0;
+            while cells <= area {
+                let shape = rng.random_range(0..sizes.len());
+                counts[shape] += 1;
+                cells += sizes[shape];
+            }
+        }
+        let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+        writeln!(input, "{}x{}: {}", width, height, counts.join(" ")).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn day6_options(rows: u32) -> Day6Options {
+        Day6Options {
+            problems: 50,
+            rows,
+            max_digits: 4,
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_input() {
+        let options = day6_options(4);
+        let first = day6(&mut ChaCha8Rng::seed_from_u64(7), &options);
+        let second = day6(&mut ChaCha8Rng::seed_from_u64(7), &options);
+        let other = day6(&mut ChaCha8Rng::seed_from_u64(8), &options);
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn day6_lines_up_every_row_with_the_operators() {
+        for rows in 1..=4 {
+            let input = day6(&mut ChaCha8Rng::seed_from_u64(0), &day6_options(rows));
+            let lines: Vec<&str> = input.lines().collect();
+            assert_eq!(lines.len(), rows as usize + 1);
+            assert!(lines.iter().all(|line| line.len() == lines[0].len()));
+        }
+    }
+
+    #[test]
+    fn day6_rows_are_bounded() {
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            options: Day6Options,
+        }
+
+        assert!(Cli::try_parse_from(["day6", "--rows", "4"]).is_ok());
+        assert!(Cli::try_parse_from(["day6", "--rows", "5"]).is_err());
+    }
+}
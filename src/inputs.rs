@@ -0,0 +1,162 @@
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Overrides the directory puzzle inputs are cached in; a relative path is
+/// taken from the crate root
+pub const DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// The cache directory relative to the crate root when `AOC_INPUT_DIR` is unset
+pub const DEFAULT_DIR: &str = "inputs";
+
+#[derive(Debug)]
+pub enum InputError {
+    /// Input names are file stems, so they cannot contain path separators
+    InvalidName(String),
+    NotFound(PathBuf),
+    NotUtf8(PathBuf),
+    Empty(PathBuf),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::InvalidName(name) => write!(f, "{:?} is not a valid input name", name),
+            InputError::NotFound(path) => write!(f, "No input at {}", path.display()),
+            InputError::NotUtf8(path) => write!(f, "{} is not valid UTF-8", path.display()),
+            InputError::Empty(path) => write!(f, "{} is empty", path.display()),
+            InputError::Io(path, e) => write!(f, "Could not access {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// The directory holding every day's inputs
+pub fn cache_dir() -> PathBuf {
+    match env::var_os(DIR_VAR) {
+        // Joining keeps an absolute directory as it is
+        Some(dir) => Path::new(env!("CARGO_MANIFEST_DIR")).join(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR")).join(DEFAULT_DIR),
+    }
+}
+
+/// Where the input called `name` for `day` lives, relative to the cache
+/// directory, e.g. `day5/example.txt`
+pub fn relative_path(day: u8, name: &str) -> PathBuf {
+    Path::new(&format!("day{}", day)).join(format!("{}.txt", name))
+}
+
+/// Where the input called `name` for `day` is cached
+pub fn path(day: u8, name: &str) -> PathBuf {
+    cache_dir().join(relative_path(day, name))
+}
+
+/// Loads the cached input called `name` for `day`, such as `example`, `real`
+/// or `stress`
+pub fn load(day: u8, name: &str) -> Result<String, InputError> {
+    check_name(name)?;
+    load_path(&path(day, name))
+}
+
+/// Writes `contents` to the cache as the input called `name` for `day`,
+/// replacing any existing input of that name, and returns where it went
+pub fn save(day: u8, name: &str, contents: &str) -> Result<PathBuf, InputError> {
+    check_name(name)?;
+    let path = path(day, name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| InputError::Io(dir.to_path_buf(), e))?;
+    }
+    fs::write(&path, contents).map_err(|e| InputError::Io(path.clone(), e))?;
+    Ok(path)
+}
+
+fn check_name(name: &str) -> Result<(), InputError> {
+    if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err(InputError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Loads an input file, converting `\r\n` line endings to `\n` and ending it
+/// with exactly one newline. Fails if the file is missing, is not UTF-8 or
+/// holds nothing but newlines.
+pub fn load_path(path: &Path) -> Result<String, InputError> {
+    let bytes = fs::read(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => InputError::NotFound(path.to_path_buf()),
+        _ => InputError::Io(path.to_path_buf(), e),
+    })?;
+    let text = String::from_utf8(bytes).map_err(|_| InputError::NotUtf8(path.to_path_buf()))?;
+    normalize(&text).ok_or_else(|| InputError::Empty(path.to_path_buf()))
+}
+
+/// Converts `\r\n` line endings to `\n` and ends the text with exactly one
+/// newline, or returns `None` if it holds nothing but newlines
+pub fn normalize(text: &str) -> Option<String> {
+    let mut text = text.replace("\r\n", "\n");
+    text.truncate(text.trim_end_matches('\n').len());
+    if text.is_empty() {
+        return None;
+    }
+    text.push('\n');
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    #[test]
+    fn normalize_converts_crlf() {
+        assert_eq!(normalize("1\r\n2\r\n").as_deref(), Some("1\n2\n"));
+    }
+
+    #[test]
+    fn normalize_ends_with_one_newline() {
+        assert_eq!(normalize("1\n2").as_deref(), Some("1\n2\n"));
+        assert_eq!(normalize("1\n2\n\n\r\n").as_deref(), Some("1\n2\n"));
+        // Blank lines inside the text are kept
+        assert_eq!(normalize("1\n\n2\n").as_deref(), Some("1\n\n2\n"));
+    }
+
+    #[test]
+    fn normalize_rejects_empty_text() {
+        assert_eq!(normalize(""), None);
+        assert_eq!(normalize("\n\r\n\n"), None);
+    }
+
+    #[test]
+    fn names_must_be_file_stems() {
+        for name in ["example", "real", "stress-2", "big.v2"] {
+            assert!(check_name(name).is_ok(), "{name} should be valid");
+        }
+        for name in ["", ".", "..", "a/b", "..\\real", "/etc/passwd"] {
+            assert!(
+                matches!(check_name(name), Err(InputError::InvalidName(_))),
+                "{name} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn load_path_rejects_empty_and_missing_files() {
+        let dir = env::temp_dir().join(format!("aoc-inputs-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let empty = dir.join("empty.txt");
+        fs::write(&empty, "\r\n\n").unwrap();
+        assert!(matches!(load_path(&empty), Err(InputError::Empty(_))));
+
+        let crlf = dir.join("crlf.txt");
+        fs::write(&crlf, "1\r\n2").unwrap();
+        assert_eq!(load_path(&crlf).unwrap(), "1\n2\n");
+
+        let missing = dir.join("missing.txt");
+        assert!(matches!(load_path(&missing), Err(InputError::NotFound(_))));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
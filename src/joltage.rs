@@ -0,0 +1,120 @@
+/// The largest number of digits whose joltage is guaranteed to fit in a `u128`
+pub const MAX_DIGITS: usize = 38;
+
+/// The batteries chosen from a bank and the joltage they produce
+#[derive(Debug, PartialEq, Eq)]
+pub struct Joltage {
+    pub value: u128,
+    /// Indices into the bank of the chosen batteries, in increasing order
+    pub indices: Vec<usize>,
+}
+
+/// Picks `n` batteries from `bank`, keeping their order, so that the digits
+/// read left to right form the largest possible number.
+///
+/// Uses a monotonic stack: a battery is dropped whenever a larger one comes
+/// after it and there are still enough batteries left to fill all `n` slots.
+/// Returns `None` if the bank has fewer than `n` batteries or `n` is larger
+/// than [`MAX_DIGITS`].
+pub fn max_joltage(bank: &[u32], n: usize) -> Option<Joltage> {
+    if n > bank.len() || n > MAX_DIGITS {
+        return None;
+    }
+
+    let mut indices: Vec<usize> = Vec::with_capacity(n);
+    for (i, battery) in bank.iter().enumerate() {
+        let remaining = bank.len() - i;
+        while let Some(&top) = indices.last() {
+            if bank[top] < *battery && indices.len() - 1 + remaining >= n {
+                indices.pop();
+            } else {
+                break;
+            }
+        }
+        if indices.len() < n {
+            indices.push(i);
+        }
+    }
+
+    let value = indices
+        .iter()
+        .fold(0u128, |acc, &i| acc * 10 + bank[i] as u128);
+    Some(Joltage { value, indices })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const EXAMPLE: [&str; 4] = [
+        "987654321111111",
+        "811111111111119",
+        "234234234234278",
+        "818181911112111",
+    ];
+
+    fn bank(digits: &str) -> Vec<u32> {
+        digits.chars().map(|c| c.to_digit(10).unwrap()).collect()
+    }
+
+    /// The greedy selection: each battery is the leftmost largest one that
+    /// still leaves enough batteries after it for the remaining slots
+    fn greedy_indices(bank: &[u32], n: usize) -> Vec<usize> {
+        let mut indices = Vec::with_capacity(n);
+        let mut start = 0;
+        for slot in 0..n {
+            let end = bank.len() - (n - slot - 1);
+            let best = (start..end).rev().max_by_key(|&i| bank[i]).unwrap();
+            indices.push(best);
+            start = best + 1;
+        }
+        indices
+    }
+
+    #[test]
+    fn example_totals() {
+        let total = |n| -> u128 {
+            EXAMPLE
+                .iter()
+                .map(|digits| max_joltage(&bank(digits), n).unwrap().value)
+                .sum()
+        };
+        assert_eq!(total(2), 357);
+        assert_eq!(total(12), 3121910778619);
+    }
+
+    #[test]
+    fn whole_bank_is_kept_in_order() {
+        let bank = bank("818181911112111");
+        let joltage = max_joltage(&bank, bank.len()).unwrap();
+        assert_eq!(joltage.value, 818181911112111);
+        assert_eq!(joltage.indices, (0..bank.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn max_digits_fits() {
+        let bank = vec![9; MAX_DIGITS + 2];
+        let joltage = max_joltage(&bank, MAX_DIGITS).unwrap();
+        assert_eq!(joltage.value, 10u128.pow(MAX_DIGITS as u32) - 1);
+        assert_eq!(joltage.indices, (0..MAX_DIGITS).collect::<Vec<_>>());
+        assert_eq!(max_joltage(&bank, MAX_DIGITS + 1), None);
+    }
+
+    #[test]
+    fn short_banks_are_rejected() {
+        assert_eq!(max_joltage(&bank("12"), 3), None);
+    }
+
+    proptest! {
+        #[test]
+        fn picks_the_same_batteries_as_greedy(
+            bank in prop::collection::vec(1..=9u32, 1..60),
+            n in 1..=MAX_DIGITS,
+        ) {
+            prop_assume!(n <= bank.len());
+            let joltage = max_joltage(&bank, n).unwrap();
+            prop_assert_eq!(joltage.indices, greedy_indices(&bank, n));
+        }
+    }
+}
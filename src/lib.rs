@@ -1,9 +1,32 @@
+use std::path::PathBuf;
+
 use clap::Parser;
 
+pub mod generate;
+pub mod inputs;
+pub mod joltage;
+pub mod range_set;
+pub mod scaffold;
+
 /// Advent of Code 2025
 #[derive(Parser)]
 #[command(about)]
 pub struct Args {
-    /// the input file to use
-    pub input: String,
+    /// the input file to use instead of one from the input cache
+    pub input: Option<PathBuf>,
+
+    /// which cached input to use for the day, e.g. example, real or stress
+    #[arg(long, default_value = "real", conflicts_with = "input")]
+    pub input_name: String,
+}
+
+impl Args {
+    /// Loads the input file if one was given, otherwise the cached input for
+    /// `day` selected by `--input-name`
+    pub fn load_input(&self, day: u8) -> Result<String, inputs::InputError> {
+        match &self.input {
+            Some(path) => inputs::load_path(path),
+            None => inputs::load(day, &self.input_name),
+        }
+    }
 }
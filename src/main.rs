@@ -0,0 +1,119 @@
+use std::{path::Path, process};
+
+use advent_of_code_2025::{generate, inputs, scaffold};
+use clap::{Parser, Subcommand};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// Advent of Code 2025 project tools
+#[derive(Parser)]
+#[command(about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a solution and an empty example input for a new day
+    NewDay {
+        /// the day to create
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Generate a random input for a day
+    Generate {
+        #[command(subcommand)]
+        day: GenerateDay,
+
+        /// the seed to generate from; a random one is picked and printed if
+        /// not given
+        #[arg(long, global = true)]
+        seed: Option<u64>,
+
+        /// save the input to the input cache under this name, e.g. stress,
+        /// instead of printing it
+        #[arg(long, global = true)]
+        input_name: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum GenerateDay {
+    Day1(generate::Day1Options),
+    Day2(generate::Day2Options),
+    Day3(generate::Day3Options),
+    Day4(generate::GridOptions),
+    Day5(generate::Day5Options),
+    Day6(generate::Day6Options),
+    Day7(generate::GridOptions),
+    Day8(generate::Day8Options),
+    Day9(generate::Day9Options),
+    Day10(generate::Day10Options),
+    Day11(generate::Day11Options),
+    Day12(generate::Day12Options),
+}
+
+impl GenerateDay {
+    fn generate(&self, rng: &mut ChaCha8Rng) -> (u8, String) {
+        match self {
+            GenerateDay::Day1(options) => (1, generate::day1(rng, options)),
+            GenerateDay::Day2(options) => (2, generate::day2(rng, options)),
+            GenerateDay::Day3(options) => (3, generate::day3(rng, options)),
+            GenerateDay::Day4(options) => (4, generate::day4(rng, options)),
+            GenerateDay::Day5(options) => (5, generate::day5(rng, options)),
+            GenerateDay::Day6(options) => (6, generate::day6(rng, options)),
+            GenerateDay::Day7(options) => (7, generate::day7(rng, options)),
+            GenerateDay::Day8(options) => (8, generate::day8(rng, options)),
+            GenerateDay::Day9(options) => (9, generate::day9(rng, options)),
+            GenerateDay::Day10(options) => (10, generate::day10(rng, options)),
+            GenerateDay::Day11(options) => (11, generate::day11(rng, options)),
+            GenerateDay::Day12(options) => (12, generate::day12(rng, options)),
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::NewDay { day } => {
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+            match scaffold::new_day(root, &inputs::cache_dir(), day) {
+                Ok(paths) => {
+                    for path in paths {
+                        println!("Created {}", path.display());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Could not create day {}: {}", day, e);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Generate {
+            day,
+            seed,
+            input_name,
+        } => {
+            let seed = seed.unwrap_or_else(|| {
+                let seed = rand::random();
+                eprintln!("Seed: {}", seed);
+                seed
+            });
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let (day, input) = day.generate(&mut rng);
+
+            match input_name {
+                Some(name) => match inputs::save(day, &name, &input) {
+                    Ok(path) => println!("Created {}", path.display()),
+                    Err(e) => {
+                        eprintln!("Could not save day {} input: {}", day, e);
+                        process::exit(1);
+                    }
+                },
+                None => print!("{}", input),
+            }
+        }
+    }
+}
@@ -0,0 +1,317 @@
+use std::ops::RangeInclusive;
+
+/// Integers that can be stored in a [`RangeSet`]
+pub trait Integer: Copy + Ord {
+    const MIN: Self;
+    const MAX: Self;
+
+    fn checked_succ(self) -> Option<Self>;
+    fn checked_pred(self) -> Option<Self>;
+
+    /// How many values lie in `lo..=hi`, which must not be empty
+    fn span(lo: Self, hi: Self) -> u128;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const MIN: Self = <$t>::MIN;
+                const MAX: Self = <$t>::MAX;
+
+                fn checked_succ(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn checked_pred(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn span(lo: Self, hi: Self) -> u128 {
+                    (hi as i128 - lo as i128) as u128 + 1
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// A set of integers stored as sorted, disjoint inclusive intervals.
+///
+/// Intervals that overlap or touch are merged on insertion, so two sets with
+/// the same members always have the same intervals.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    intervals: Vec<(T, T)>,
+    len: u128,
+}
+
+impl<T: Integer> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet::new()
+    }
+}
+
+/// Whether an interval ending at `hi` overlaps or touches one starting at `lo`
+fn joins<T: Integer>(hi: T, lo: T) -> bool {
+    hi >= lo || hi.checked_succ() == Some(lo)
+}
+
+impl<T: Integer> RangeSet<T> {
+    pub fn new() -> RangeSet<T> {
+        RangeSet {
+            intervals: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Builds a set from intervals sorted by their start, merging as it goes
+    fn from_sorted(intervals: impl IntoIterator<Item = (T, T)>) -> RangeSet<T> {
+        let mut set = RangeSet::new();
+        for (lo, hi) in intervals {
+            match set.intervals.last_mut() {
+                Some(last) if joins(last.1, lo) => {
+                    if hi > last.1 {
+                        set.len -= T::span(last.0, last.1);
+                        last.1 = hi;
+                        set.len += T::span(last.0, last.1);
+                    }
+                }
+                _ => {
+                    set.len += T::span(lo, hi);
+                    set.intervals.push((lo, hi));
+                }
+            }
+        }
+        set
+    }
+
+    /// Adds every value in `range` to the set. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut lo, mut hi) = range.into_inner();
+        if lo > hi {
+            return;
+        }
+
+        let first = self.intervals.partition_point(|&(_, end)| !joins(end, lo));
+        let last = self
+            .intervals
+            .partition_point(|&(start, _)| joins(hi, start));
+        for &(start, end) in &self.intervals[first..last] {
+            self.len -= T::span(start, end);
+            lo = lo.min(start);
+            hi = hi.max(end);
+        }
+        self.len += T::span(lo, hi);
+        self.intervals.splice(first..last, [(lo, hi)]);
+    }
+
+    /// Checks membership with a binary search over the intervals
+    pub fn contains(&self, value: T) -> bool {
+        self.find(value).is_some()
+    }
+
+    /// Returns the interval containing `value`, if any
+    pub fn find(&self, value: T) -> Option<RangeInclusive<T>> {
+        let i = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals
+            .get(i)
+            .filter(|&&(start, _)| start <= value)
+            .map(|&(start, end)| start..=end)
+    }
+
+    /// The number of values in the set
+    pub fn len(&self) -> u128 {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of disjoint intervals making up the set
+    pub fn range_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    /// The disjoint intervals making up the set, in increasing order
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut merged = Vec::with_capacity(self.intervals.len() + other.intervals.len());
+        let (mut a, mut b) = (
+            self.intervals.iter().peekable(),
+            other.intervals.iter().peekable(),
+        );
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            if x.0 <= y.0 {
+                merged.push(*a.next().unwrap());
+            } else {
+                merged.push(*b.next().unwrap());
+            }
+        }
+        merged.extend(a);
+        merged.extend(b);
+        RangeSet::from_sorted(merged)
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut overlaps = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a_lo, a_hi) = self.intervals[i];
+            let (b_lo, b_hi) = other.intervals[j];
+            let lo = a_lo.max(b_lo);
+            let hi = a_hi.min(b_hi);
+            if lo <= hi {
+                overlaps.push((lo, hi));
+            }
+            if a_hi < b_hi {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet::from_sorted(overlaps)
+    }
+
+    /// Every value of `T` that is not in the set
+    pub fn complement(&self) -> RangeSet<T> {
+        let mut gaps = Vec::with_capacity(self.intervals.len() + 1);
+        let mut next = Some(T::MIN);
+        for &(start, end) in &self.intervals {
+            if let Some(lo) = next
+                && let Some(hi) = start.checked_pred()
+                && lo <= hi
+            {
+                gaps.push((lo, hi));
+            }
+            next = end.checked_succ();
+        }
+        if let Some(lo) = next {
+            gaps.push((lo, T::MAX));
+        }
+        RangeSet::from_sorted(gaps)
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        self.intersection(&other.complement())
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    fn ranges<T: Integer>(set: &RangeSet<T>) -> Vec<RangeInclusive<T>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn touching_and_overlapping_ranges_merge() {
+        let mut set = RangeSet::new();
+        set.insert(10..=14u32);
+        set.insert(16..=20);
+        assert_eq!(ranges(&set), [10..=14, 16..=20]);
+        set.insert(15..=15);
+        assert_eq!(ranges(&set), [10..=20]);
+        set.insert(18..=25);
+        set.insert(3..=9);
+        assert_eq!(ranges(&set), [3..=25]);
+        assert_eq!(set.len(), 23);
+    }
+
+    #[test]
+    fn insert_spanning_several_ranges_merges_them_all() {
+        let mut set: RangeSet<i32> = [0..=1, 4..=5, 8..=9, 20..=21].into_iter().collect();
+        set.insert(1..=8);
+        assert_eq!(ranges(&set), [0..=9, 20..=21]);
+        assert_eq!(set.len(), 12);
+        assert_eq!(set.range_count(), 2);
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn reversed_ranges_are_ignored() {
+        let set: RangeSet<u8> = [5..=3, 7..=7].into_iter().collect();
+        assert_eq!(ranges(&set), [7..=7]);
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn find_returns_the_containing_range() {
+        let set: RangeSet<u64> = [3..=5, 10..=14].into_iter().collect();
+        assert_eq!(set.find(12), Some(10..=14));
+        assert_eq!(set.find(3), Some(3..=5));
+        assert_eq!(set.find(7), None);
+        assert!(!set.contains(15));
+    }
+
+    #[test]
+    fn complement_at_the_type_bounds() {
+        let empty = RangeSet::<u8>::new();
+        assert_eq!(ranges(&empty.complement()), [0..=255]);
+        assert_eq!(empty.complement().len(), 256);
+        assert!(empty.complement().complement().is_empty());
+
+        let ends: RangeSet<i8> = [-128..=-100, 100..=127].into_iter().collect();
+        assert_eq!(ranges(&ends.complement()), [-99..=99]);
+
+        let middle: RangeSet<i8> = [0..=0].into_iter().collect();
+        assert_eq!(ranges(&middle.complement()), [-128..=-1, 1..=127]);
+    }
+
+    #[test]
+    fn full_range_length_does_not_overflow() {
+        let set: RangeSet<u64> = [0..=u64::MAX].into_iter().collect();
+        assert_eq!(set.len(), u64::MAX as u128 + 1);
+        assert!(set.complement().is_empty());
+    }
+
+    fn members(set: &RangeSet<u8>) -> BTreeSet<u8> {
+        set.ranges().flatten().collect()
+    }
+
+    fn range_set() -> impl Strategy<Value = (RangeSet<u8>, BTreeSet<u8>)> {
+        prop::collection::vec((any::<u8>(), any::<u8>()), 0..8).prop_map(|pairs| {
+            let set: RangeSet<u8> = pairs.iter().map(|&(lo, hi)| lo..=hi).collect();
+            let reference = pairs.iter().flat_map(|&(lo, hi)| lo..=hi).collect();
+            (set, reference)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn matches_a_btree_set(
+            (a, a_ref) in range_set(),
+            (b, b_ref) in range_set(),
+        ) {
+            prop_assert_eq!(members(&a), a_ref.clone());
+            prop_assert_eq!(a.len(), a_ref.len() as u128);
+            prop_assert_eq!(members(&a.union(&b)), &a_ref | &b_ref);
+            prop_assert_eq!(members(&a.intersection(&b)), &a_ref & &b_ref);
+            prop_assert_eq!(members(&a.difference(&b)), &a_ref - &b_ref);
+            let all: BTreeSet<u8> = (0..=u8::MAX).collect();
+            prop_assert_eq!(members(&a.complement()), &all - &a_ref);
+            // Merged sets never keep touching intervals apart
+            for pair in a.intervals.windows(2) {
+                prop_assert!(!joins(pair[0].1, pair[1].0));
+            }
+        }
+    }
+}
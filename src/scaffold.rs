@@ -0,0 +1,138 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::inputs;
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+
+/// Where the solution for `day` lives, relative to the crate root
+pub fn solution_path(day: u8) -> PathBuf {
+    Path::new("src").join("bin").join(format!("day{}.rs", day))
+}
+
+/// How the solution refers to `example` in `include_str!`, relative to
+/// `src/bin`. Fails if the example is outside `root`, as the solution would
+/// then only compile on this machine.
+fn include_path(root: &Path, example: &Path) -> io::Result<String> {
+    let relative = example.strip_prefix(root).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "{} is outside {}, so the solution could not include it",
+                example.display(),
+                root.display()
+            ),
+        )
+    })?;
+    Ok(Path::new("../..")
+        .join(relative)
+        .to_string_lossy()
+        .replace('\\', "/"))
+}
+
+fn create_new(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    // create_new guards against a file appearing since new_day checked
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)?
+        .write_all(contents.as_bytes())
+}
+
+/// Writes a solution skeleton for `day` under `root` and an empty example
+/// input under `cache_dir`, the directory [`inputs::load`] reads from,
+/// returning the paths created. A relative `cache_dir` is taken from `root`.
+/// Fails without leaving anything behind if either file already exists or
+/// cannot be written, or if the example would be outside `root`.
+pub fn new_day(root: &Path, cache_dir: &Path, day: u8) -> io::Result<Vec<PathBuf>> {
+    let solution = root.join(solution_path(day));
+    let example = root
+        .join(cache_dir)
+        .join(inputs::relative_path(day, "example"));
+    let include = include_path(root, &example)?;
+    for path in [&solution, &example] {
+        if path.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", path.display()),
+            ));
+        }
+    }
+
+    let source = DAY_TEMPLATE
+        .replace("{{day}}", &day.to_string())
+        .replace("{{example}}", &include);
+    create_new(&solution, &source)?;
+    if let Err(e) = create_new(&example, "") {
+        // A solution without its example would not compile its tests
+        let _ = fs::remove_file(&solution);
+        return Err(e);
+    }
+
+    Ok(vec![solution, example])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    /// A fresh directory under the system temp directory
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}-{}", process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn example_inside_root_is_included_relatively() {
+        let root = scratch_dir("inside");
+        let paths = new_day(&root, &root.join(inputs::DEFAULT_DIR), 13).unwrap();
+        assert_eq!(paths[1], root.join("inputs/day13/example.txt"));
+        let source = fs::read_to_string(&paths[0]).unwrap();
+        assert!(source.contains(r#"include_str!("../../inputs/day13/example.txt")"#));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn relative_cache_dir_is_taken_from_the_root() {
+        let root = scratch_dir("relative");
+        let paths = new_day(&root, Path::new("cache"), 13).unwrap();
+        assert_eq!(paths[1], root.join("cache/day13/example.txt"));
+        assert!(paths[1].exists());
+        let source = fs::read_to_string(&paths[0]).unwrap();
+        assert!(source.contains(r#"include_str!("../../cache/day13/example.txt")"#));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn refuses_a_cache_dir_outside_the_root() {
+        let root = scratch_dir("root");
+        let cache = scratch_dir("cache");
+        let err = new_day(&root, &cache, 13).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(!root.join(solution_path(13)).exists());
+        assert!(!cache.join("day13").exists());
+        fs::remove_dir_all(root).unwrap();
+        fs::remove_dir_all(cache).unwrap();
+    }
+
+    #[test]
+    fn failed_example_removes_the_solution() {
+        let root = scratch_dir("failed");
+        let cache = root.join("cache");
+        // The example's directory cannot be created over a file
+        fs::create_dir_all(&cache).unwrap();
+        fs::write(cache.join("day13"), "").unwrap();
+        assert!(new_day(&root, &cache, 13).is_err());
+        assert!(!root.join(solution_path(13)).exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}